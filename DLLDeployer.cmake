@@ -1,6 +1,9 @@
 cmake_minimum_required(VERSION 3.25)
 
-set(DLLD_deploy_dll_exe_version "1.6.0")
+# DLLD_add_deploy passes flags that older versions reject, bump it with the version in Cargo.toml
+set(DLLD_deploy_dll_exe_version "1.7.0")
+# SHA512 of the release archive of this version, empty until the archive is published
+set(DLLD_deploy_dll_archive_sha512 "")
 
 # Replace backslash \ with slash /
 function(DLLD_replace_backslash in_var out_var)
@@ -68,11 +71,15 @@ function(DLLD_get_deploy_dll_exe out_deploy_dll)
         return()
     endif ()
 
+    if(NOT DLLD_deploy_dll_archive_sha512)
+        message(FATAL_ERROR "deploy-dll ${DLLD_deploy_dll_exe_version} or newer is not found and its release archive is not published yet, install it by \"cargo install --path <SharedLibDeployer source dir>\"")
+    endif ()
+
     message(STATUS "Downloading and extracting SharedLibDeployer-${DLLD_deploy_dll_exe_version}-win64.7z")
     set(archive_loc "${PROJECT_BINARY_DIR}/SharedLibDeployer-${DLLD_deploy_dll_exe_version}-win64.7z")
     file(DOWNLOAD https://github.com/SlopeCraft/SharedLibDeployer/releases/download/v${DLLD_deploy_dll_exe_version}/SharedLibDeployer-${DLLD_deploy_dll_exe_version}-win64.7z
         ${archive_loc}
-        EXPECTED_HASH SHA512=003E49903F6C40874F0E9F78F5870978AF846D858807AD315506BA876DD393AFB82788DCE987055EC8FC52723132A1DBF48570B7356379A05B108068EE7F1215
+        EXPECTED_HASH SHA512=${DLLD_deploy_dll_archive_sha512}
     )
 
     file(ARCHIVE_EXTRACT INPUT ${archive_loc} DESTINATION ${extract_destination})
@@ -131,33 +138,41 @@ function(DLLD_add_deploy target_name)
         list(APPEND flags "--copy-vc-redist")
     endif ()
 
-    if(DEFINED CMAKE_C_COMPILER)
-        cmake_path(GET CMAKE_C_COMPILER PARENT_PATH c_compiler_path)
-        if(c_compiler_path)
-            list(APPEND flags "\"--shallow-search-dir=${c_compiler_path}\"")
-        endif ()
-    endif()
-    if(DEFINED CMAKE_CXX_COMPILER)
-    cmake_path(GET CMAKE_CXX_COMPILER PARENT_PATH cxx_compiler_path)
-    if((cxx_compiler_path) AND (NOT c_compiler_path STREQUAL cxx_compiler_path))
-        list(APPEND flags "\"--shallow-search-dir=${cxx_compiler_path}\"")
-    endif ()
-    endif()
+    # Compilers, build type, package dirs and the build dir are read from the cache
+    list(APPEND flags "--cmake-cache=${CMAKE_BINARY_DIR}")
 
-    list(APPEND flags "\"--deep-search-dir=${CMAKE_BINARY_DIR}\"")
+    # The target triple is usually set by the toolchain file, which is not cached
+    if(CMAKE_CXX_COMPILER_TARGET)
+        list(APPEND flags "--target-triple=${CMAKE_CXX_COMPILER_TARGET}")
+    elseif(CMAKE_C_COMPILER_TARGET)
+        list(APPEND flags "--target-triple=${CMAKE_C_COMPILER_TARGET}")
+    endif ()
 
     foreach (item ${DLLD_add_deploy_OPTIONAL_DLLS})
-        list(APPEND flags "\"--optional-dlls=${item}\"")
+        list(APPEND flags "--optional-dlls=${item}")
     endforeach ()
 
     DLLD_replace_backslash(CMAKE_PREFIX_PATH CMAKE_PREFIX_PATH)
 
     foreach (path ${CMAKE_PREFIX_PATH})
-        list(APPEND flags "\"--cmake-prefix-path=${path}\"")
+        list(APPEND flags "--cmake-prefix-path=${path}")
     endforeach ()
 
     list(APPEND flags ${DLLD_add_deploy_FLAGS})
 
+    # Pass flags by a response file, long command lines fail on Windows. It is generated for
+    # every config, so that generator expressions in flags are evaluated
+    set(response_file "${CMAKE_CURRENT_BINARY_DIR}/DLLD_deploy_for_${target_name}-$<CONFIG>.rsp")
+    set(response_file_content "")
+    foreach (flag ${flags})
+        string(REPLACE "\\" "\\\\" flag "${flag}")
+        string(REPLACE "\"" "\\\"" flag "${flag}")
+        string(APPEND response_file_content "\"${flag}\"\n")
+    endforeach ()
+    file(GENERATE OUTPUT "${response_file}"
+        CONTENT "${response_file_content}"
+        TARGET ${target_name})
+
     if(${DLLD_add_deploy_BUILD_MODE})
         set(custom_target_name "DLLD_deploy_for_${target_name}")
         if (${DLLD_add_deploy_ALL})
@@ -170,11 +185,10 @@ function(DLLD_add_deploy target_name)
 
         add_custom_target(${custom_target_name}
             ${DLLD_all_tag}
-            COMMAND ${DLLD_deploy_dll_executable_location} ${filename} ${flags}
+            COMMAND ${DLLD_deploy_dll_executable_location} ${filename} "@${response_file}" --allow-non-redistributable
             WORKING_DIRECTORY ${target_binary_dir}
             DEPENDS ${target_name}
-            COMMENT "Deploy dll for ${target_name} at build directory"
-            COMMAND_EXPAND_LISTS)
+            COMMENT "Deploy dll for ${target_name} at build directory")
 
         if(NOT TARGET DLLD_deploy_all)
             add_custom_target(DLLD_deploy_all
@@ -189,11 +203,9 @@ function(DLLD_add_deploy target_name)
 
     if(${DLLD_add_deploy_INSTALL_MODE})
 
-        string(JOIN " " flags ${flags})
-
         install(CODE
             "
-            execute_process(COMMAND \"${DLLD_deploy_dll_executable_location}\" \"./${DLLD_add_deploy_INSTALL_DESTINATION}/${filename}\" ${flags}
+            execute_process(COMMAND \"${DLLD_deploy_dll_executable_location}\" \"./${DLLD_add_deploy_INSTALL_DESTINATION}/${filename}\" \"@${response_file}\"
                 WORKING_DIRECTORY \${CMAKE_INSTALL_PREFIX}
                 COMMAND_ERROR_IS_FATAL ANY)
             ")
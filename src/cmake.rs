@@ -0,0 +1,553 @@
+use crate::error::Error;
+use crate::resolver::BuildConfig;
+use crate::util::{can_be_dir, long_path, normalize_separators, subdirs, walk_dir};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Name of the cache file in a CMake build dir
+pub const CMAKE_CACHE_FILE_NAME: &str = "CMakeCache.txt";
+
+/// Dirs under a prefix where CMake package configs are installed
+const PACKAGE_CONFIG_DIRS: [&str; 4] = ["lib/cmake", "lib64/cmake", "share", "cmake"];
+
+/// Architecture dirs some packages put their binaries in, like `<prefix>/x64/vc17/bin` of OpenCV
+const ARCH_DIRS: [&str; 4] = ["x64", "x86", "arm64", "arm"];
+
+/// Configs of multi-config generators, like `<prefix>/bin/Release`
+const RELEASE_CONFIGS: [&str; 3] = ["Release", "RelWithDebInfo", "MinSizeRel"];
+
+/// A dll of an imported target, read from `IMPORTED_LOCATION_<CONFIG>` in a
+/// `<Package>Targets-<config>.cmake` file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedDll {
+    /// Name of the imported target, like `ZLIB::ZLIB`
+    pub target: String,
+    /// Upper case config, like `RELEASE`, or `NOCONFIG` for `IMPORTED_LOCATION`
+    pub config: String,
+    pub path: PathBuf,
+}
+
+impl ImportedDll {
+    pub fn build_config(&self) -> BuildConfig {
+        return if self.config == "DEBUG" {
+            BuildConfig::Debug
+        } else {
+            BuildConfig::Release
+        };
+    }
+}
+
+/// Dirs of a CMake prefix that may have dlls, dirs of `config` first. Besides `<prefix>/bin`,
+/// packages put dlls in `bin/<config>`, `lib`, or architecture dirs like `<prefix>/x64/bin` and
+/// `<prefix>/x64/vc17/bin`.
+pub fn prefix_dll_dirs(prefix: &Path, config: BuildConfig) -> Vec<PathBuf> {
+    let bin = prefix.join("bin");
+    let release: Vec<PathBuf> = RELEASE_CONFIGS.iter().map(|c| bin.join(c)).collect();
+    let debug = vec![bin.join("Debug")];
+    let (first, second) = match config {
+        BuildConfig::Release => (release, debug),
+        BuildConfig::Debug => (debug, release),
+    };
+
+    let mut dirs = vec![bin.clone()];
+    dirs.extend(first);
+    dirs.push(prefix.join("lib"));
+    for arch in ARCH_DIRS {
+        let arch_dir = prefix.join(arch);
+        dirs.push(arch_dir.join("bin"));
+        // Newest runtime first, like vc17 before vc16
+        let mut runtimes = subdirs(&arch_dir);
+        runtimes.reverse();
+        dirs.extend(runtimes.iter().map(|d| d.join("bin")));
+    }
+    dirs.extend(second);
+    dirs.retain(can_be_dir);
+    return dirs;
+}
+
+/// Dlls of imported targets exported by packages under `prefix`, found in
+/// `<Package>Targets-<config>.cmake` files in the package config dirs.
+pub fn imported_dlls(prefix: &Path) -> Vec<ImportedDll> {
+    let mut dlls = Vec::new();
+    for dir in PACKAGE_CONFIG_DIRS {
+        let dir = prefix.join(dir);
+        if !can_be_dir(&dir) {
+            continue;
+        }
+        for entry in walk_dir(&dir, |w| w.max_depth(3).sort_by_file_name()) {
+            let (path, entry) = match entry {
+                Ok(entry) => entry,
+                Err(_) => continue,
+            };
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            // Like `ZLIBTargets-release.cmake` or `zlib-targets-release.cmake`
+            let is_targets_file = name.ends_with(".cmake") && name.contains("targets-");
+            if is_targets_file && entry.file_type().is_file() {
+                dlls.extend(parse_targets_file(&path, prefix));
+            }
+        }
+    }
+    return dlls;
+}
+
+/// Read `IMPORTED_LOCATION[_<CONFIG>]` properties that point to dlls. `${_IMPORT_PREFIX}` is
+/// computed like the `<Package>Targets.cmake` next to the file does, or is `prefix` if that
+/// can't be read.
+fn parse_targets_file(file: &Path, prefix: &Path) -> Vec<ImportedDll> {
+    let text = match std::fs::read_to_string(long_path(file)) {
+        Ok(text) => text,
+        Err(_) => return Vec::new(),
+    };
+    let list_dir = file.parent().unwrap_or(Path::new(""));
+    let import_prefix = import_prefix(file).unwrap_or_else(|| prefix.to_path_buf());
+    let import_prefix = import_prefix.to_string_lossy().replace('\\', "/");
+    let list_dir = list_dir.to_string_lossy().replace('\\', "/");
+
+    let mut dlls = Vec::new();
+    let mut target = String::new();
+    for line in text.lines() {
+        let line = line.trim();
+        if let Some(rest) = line.strip_prefix("set_target_properties(") {
+            target = rest
+                .split_whitespace()
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            continue;
+        }
+        let rest = match line.strip_prefix("IMPORTED_LOCATION") {
+            Some(rest) => rest,
+            None => continue,
+        };
+        let (config, value) = match rest.split_once(char::is_whitespace) {
+            Some((config, value)) => (config, value.trim()),
+            None => continue,
+        };
+        let config = match config.strip_prefix('_') {
+            Some(config) => config.to_uppercase(),
+            None if config.is_empty() => "NOCONFIG".to_string(),
+            None => continue,
+        };
+        let value = value.trim_matches('"');
+        if !value.to_lowercase().ends_with(".dll") {
+            continue;
+        }
+        let path = value
+            .replace("${_IMPORT_PREFIX}", &import_prefix)
+            .replace("${CMAKE_CURRENT_LIST_DIR}", &list_dir);
+        if path.contains("${") {
+            continue;
+        }
+        dlls.push(ImportedDll {
+            target: target.clone(),
+            config,
+            path: PathBuf::from(path),
+        });
+    }
+    return dlls;
+}
+
+/// `_IMPORT_PREFIX` of a config specific targets file like `ZLIBTargets-release.cmake`, computed
+/// from the `get_filename_component(_IMPORT_PREFIX ... PATH)` calls in `ZLIBTargets.cmake`.
+fn import_prefix(file: &Path) -> Option<PathBuf> {
+    let name = file.file_name()?.to_str()?;
+    let (base, _) = name.rsplit_once('-')?;
+    let main_file = file.with_file_name(format!("{base}.cmake"));
+    let text = std::fs::read_to_string(long_path(&main_file)).ok()?;
+    let levels = text
+        .lines()
+        .map(str::trim)
+        .filter(|l| l.starts_with("get_filename_component(_IMPORT_PREFIX") && l.contains("PATH"))
+        .count();
+    if levels == 0 {
+        return None;
+    }
+    let mut prefix = main_file.as_path();
+    for _ in 0..levels {
+        prefix = prefix.parent()?;
+    }
+    return Some(prefix.to_path_buf());
+}
+
+/// Entries of the `CMakeCache.txt` of a build dir, like `CMAKE_BUILD_TYPE:STRING=Release`.
+#[derive(Debug, Clone, Default)]
+pub struct CmakeCache {
+    /// The build dir the cache file is in
+    pub build_dir: PathBuf,
+    /// Type and value of every entry by key
+    entries: BTreeMap<String, (String, String)>,
+}
+
+impl CmakeCache {
+    /// Load the cache of a build dir, or a cache file given directly.
+    pub fn load(path: &Path) -> Result<CmakeCache, Error> {
+        let path = normalize_separators(path);
+        let file = if can_be_dir(&path) {
+            path.join(CMAKE_CACHE_FILE_NAME)
+        } else {
+            path
+        };
+        let text = std::fs::read_to_string(long_path(&file)).map_err(|e| {
+            return Error::io(
+                format!("Failed to read CMake cache \"{}\"", file.display()),
+                e,
+            );
+        })?;
+        let build_dir = file.parent().unwrap_or(Path::new("")).to_path_buf();
+        return Ok(CmakeCache::parse(&text, build_dir));
+    }
+
+    /// Parse lines like `KEY:TYPE=VALUE`, skipping comments
+    pub fn parse(text: &str, build_dir: PathBuf) -> CmakeCache {
+        let mut entries = BTreeMap::new();
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with("//") {
+                continue;
+            }
+            let (key, value) = match line.split_once('=') {
+                Some(entry) => entry,
+                None => continue,
+            };
+            let (key, kind) = key.split_once(':').unwrap_or((key, ""));
+            let key = key.trim_matches('"');
+            entries.insert(key.to_string(), (kind.to_string(), value.to_string()));
+        }
+        return CmakeCache { build_dir, entries };
+    }
+
+    /// Value of `key`, `None` if it is empty or `*-NOTFOUND`
+    pub fn get(&self, key: &str) -> Option<&str> {
+        let (_, value) = self.entries.get(key)?;
+        if value.is_empty() || value.ends_with("NOTFOUND") {
+            return None;
+        }
+        return Some(value);
+    }
+
+    /// The C++ compiler, or the C compiler if C++ is not enabled
+    pub fn compiler(&self) -> Option<PathBuf> {
+        return ["CMAKE_CXX_COMPILER", "CMAKE_C_COMPILER"]
+            .iter()
+            .find_map(|key| self.get(key))
+            .map(normalize_separators);
+    }
+
+    /// Dirs of the C and C++ compilers
+    pub fn compiler_dirs(&self) -> Vec<PathBuf> {
+        let mut dirs: Vec<PathBuf> = Vec::new();
+        for key in ["CMAKE_C_COMPILER", "CMAKE_CXX_COMPILER"] {
+            let compiler = match self.get(key) {
+                Some(compiler) => normalize_separators(compiler),
+                None => continue,
+            };
+            if let Some(dir) = compiler.parent() {
+                if !dirs.iter().any(|d| d == dir) {
+                    dirs.push(dir.to_path_buf());
+                }
+            }
+        }
+        return dirs;
+    }
+
+    /// Target triple of the compiler, set when cross compiling with clang
+    pub fn target_triple(&self) -> Option<String> {
+        return ["CMAKE_CXX_COMPILER_TARGET", "CMAKE_C_COMPILER_TARGET"]
+            .iter()
+            .find_map(|key| self.get(key))
+            .map(str::to_string);
+    }
+
+    /// Config of `CMAKE_BUILD_TYPE`, `None` for multi-config generators. Every build type other
+    /// than `Debug` is a release build.
+    pub fn build_config(&self) -> Option<BuildConfig> {
+        let build_type = self.get("CMAKE_BUILD_TYPE")?;
+        return Some(if build_type.eq_ignore_ascii_case("Debug") {
+            BuildConfig::Debug
+        } else {
+            BuildConfig::Release
+        });
+    }
+
+    /// `CMAKE_RUNTIME_OUTPUT_DIRECTORY` if it is cached
+    pub fn runtime_output_dir(&self) -> Option<PathBuf> {
+        return self
+            .get("CMAKE_RUNTIME_OUTPUT_DIRECTORY")
+            .map(normalize_separators);
+    }
+
+    /// Prefixes in `CMAKE_PREFIX_PATH`, the vcpkg triplet dir of `VCPKG_TARGET_TRIPLET`, and
+    /// prefixes of packages found by `find_package`, taken from `<Package>_DIR`.
+    pub fn prefix_paths(&self) -> Vec<PathBuf> {
+        let mut prefixes: Vec<PathBuf> = Vec::new();
+        let mut add = |prefix: PathBuf| {
+            if !prefixes.contains(&prefix) {
+                prefixes.push(prefix);
+            }
+        };
+        if let Some(paths) = self.get("CMAKE_PREFIX_PATH") {
+            paths
+                .split(';')
+                .filter(|p| !p.is_empty())
+                .for_each(|p| add(normalize_separators(p)));
+        }
+        let installed = ["VCPKG_INSTALLED_DIR", "_VCPKG_INSTALLED_DIR"]
+            .iter()
+            .find_map(|key| self.get(key));
+        if let (Some(installed), Some(triplet)) = (installed, self.get("VCPKG_TARGET_TRIPLET")) {
+            add(normalize_separators(installed).join(triplet));
+        }
+        for (key, (kind, _)) in &self.entries {
+            let is_package_dir = key.ends_with("_DIR")
+                && kind == "PATH"
+                && !key.starts_with("CMAKE_")
+                && !key.starts_with("VCPKG_")
+                && !key.starts_with('_');
+            if !is_package_dir {
+                continue;
+            }
+            if let Some(prefix) = self
+                .get(key)
+                .and_then(|d| package_prefix(&normalize_separators(d)))
+            {
+                add(prefix);
+            }
+        }
+        return prefixes;
+    }
+}
+
+/// Prefix of a package config dir, like `<prefix>` of `<prefix>/lib/cmake/ZLIB`,
+/// `<prefix>/share/zlib` or `<prefix>/cmake`. Otherwise the config dir itself if it has a `bin`
+/// dir, or a dir like `x64/vc16` in it that has one, as in the OpenCV package for Windows. If
+/// there are several, the last by name is used, like `x64/vc16` over `x64/vc15`.
+fn package_prefix(config_dir: &Path) -> Option<PathBuf> {
+    for dir in config_dir.ancestors().take(4) {
+        let name = match dir.file_name() {
+            Some(name) => name.to_string_lossy(),
+            None => break,
+        };
+        if ["lib", "lib64", "share"].contains(&name.as_ref()) {
+            return dir.parent().map(Path::to_path_buf);
+        }
+    }
+    if config_dir.file_name()?.eq_ignore_ascii_case("cmake") {
+        return config_dir.parent().map(Path::to_path_buf);
+    }
+    if can_be_dir(&config_dir.join("bin")) {
+        return Some(config_dir.to_path_buf());
+    }
+    return subdirs(config_dir)
+        .iter()
+        .flat_map(|d| subdirs(d))
+        .rfind(|d| can_be_dir(&d.join("bin")));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::test_dir;
+
+    const TARGETS: &str = r#"
+# Compute the installation prefix relative to this file.
+get_filename_component(_IMPORT_PREFIX "${CMAKE_CURRENT_LIST_FILE}" PATH)
+get_filename_component(_IMPORT_PREFIX "${_IMPORT_PREFIX}" PATH)
+get_filename_component(_IMPORT_PREFIX "${_IMPORT_PREFIX}" PATH)
+get_filename_component(_IMPORT_PREFIX "${_IMPORT_PREFIX}" PATH)
+if(_IMPORT_PREFIX STREQUAL "/")
+  set(_IMPORT_PREFIX "")
+endif()
+"#;
+
+    const TARGETS_RELEASE: &str = r#"
+set_property(TARGET ZLIB::ZLIB APPEND PROPERTY IMPORTED_CONFIGURATIONS RELEASE)
+set_target_properties(ZLIB::ZLIB PROPERTIES
+  IMPORTED_IMPLIB_RELEASE "${_IMPORT_PREFIX}/lib/zlib.lib"
+  IMPORTED_LOCATION_RELEASE "${_IMPORT_PREFIX}/bin/zlib1.dll"
+  )
+set_target_properties(ZLIB::zlibstatic PROPERTIES
+  IMPORTED_LOCATION_RELEASE "${_IMPORT_PREFIX}/lib/zlibstatic.lib"
+  )
+set_target_properties(ZLIB::minizip PROPERTIES
+  IMPORTED_LOCATION "${CMAKE_CURRENT_LIST_DIR}/minizip.DLL"
+  IMPORTED_LOCATION_debug "${UNKNOWN_DIR}/minizipd.dll"
+  IMPORTED_LOCATIONS_RELEASE "${_IMPORT_PREFIX}/bin/other.dll"
+  )
+"#;
+
+    #[test]
+    fn computes_import_prefix() {
+        let install = test_dir("cmake-import-prefix").join("install");
+        let config_dir = install.join("lib/cmake/ZLIB");
+        std::fs::create_dir_all(&config_dir).unwrap();
+        let release = config_dir.join("ZLIBTargets-release.cmake");
+        assert_eq!(import_prefix(&release), None);
+
+        std::fs::write(config_dir.join("ZLIBTargets.cmake"), TARGETS).unwrap();
+        assert_eq!(import_prefix(&release), Some(install.clone()));
+
+        std::fs::write(config_dir.join("ZLIBTargets.cmake"), "# nothing\n").unwrap();
+        assert_eq!(import_prefix(&release), None);
+        assert_eq!(import_prefix(&config_dir.join("targets.cmake")), None);
+    }
+
+    #[test]
+    fn parses_imported_dlls() {
+        let install = test_dir("cmake-targets").join("install");
+        let config_dir = install.join("lib/cmake/ZLIB");
+        std::fs::create_dir_all(&config_dir).unwrap();
+        std::fs::write(config_dir.join("ZLIBTargets.cmake"), TARGETS).unwrap();
+        std::fs::write(
+            config_dir.join("ZLIBTargets-release.cmake"),
+            TARGETS_RELEASE,
+        )
+        .unwrap();
+
+        let fallback = Path::new("/fallback");
+        let import_prefix = install.to_string_lossy().replace('\\', "/");
+        let list_dir = config_dir.to_string_lossy().replace('\\', "/");
+        let expected = [
+            ImportedDll {
+                target: "ZLIB::ZLIB".to_string(),
+                config: "RELEASE".to_string(),
+                path: PathBuf::from(format!("{import_prefix}/bin/zlib1.dll")),
+            },
+            ImportedDll {
+                target: "ZLIB::minizip".to_string(),
+                config: "NOCONFIG".to_string(),
+                path: PathBuf::from(format!("{list_dir}/minizip.DLL")),
+            },
+        ];
+        let release = config_dir.join("ZLIBTargets-release.cmake");
+        assert_eq!(parse_targets_file(&release, fallback), expected);
+        assert_eq!(imported_dlls(&install), expected);
+        assert_eq!(expected[0].build_config(), BuildConfig::Release);
+
+        // Without the main targets file, the prefix is the one searched
+        std::fs::remove_file(config_dir.join("ZLIBTargets.cmake")).unwrap();
+        assert_eq!(
+            parse_targets_file(&release, fallback)[0].path,
+            PathBuf::from("/fallback/bin/zlib1.dll")
+        );
+        assert!(parse_targets_file(&config_dir.join("missing.cmake"), fallback).is_empty());
+    }
+
+    const CACHE: &str = r#"
+# This is the CMakeCache file.
+//Choose the type of build.
+CMAKE_BUILD_TYPE:STRING=RelWithDebInfo
+CMAKE_CXX_COMPILER:FILEPATH=C:\llvm-mingw\bin\x86_64-w64-mingw32-clang++.exe
+CMAKE_C_COMPILER:FILEPATH=C:/llvm-mingw/bin/x86_64-w64-mingw32-clang.exe
+CMAKE_CXX_COMPILER_TARGET:STRING=
+CMAKE_C_COMPILER_TARGET:STRING=x86_64-w64-windows-gnu
+CMAKE_RUNTIME_OUTPUT_DIRECTORY:PATH=C:/build/bin
+CMAKE_PREFIX_PATH:UNINITIALIZED=C:/deps;;C:/qt/6.5.0/mingw_64
+VCPKG_INSTALLED_DIR:PATH=C:/vcpkg/installed
+VCPKG_TARGET_TRIPLET:STRING=x64-mingw-dynamic
+ZLIB_DIR:PATH=C:/vcpkg/installed/x64-mingw-dynamic/share/zlib
+Qt6_DIR:PATH=C:/qt/6.5.0/mingw_64/lib/cmake/Qt6
+OpenCV_DIR:PATH=C:/opencv/build
+fmt_DIR:PATH=C:/fmt/cmake
+Boost_DIR:PATH=Boost_DIR-NOTFOUND
+Boost_INCLUDE_DIR:PATH=C:/boost/include
+CMAKE_INSTALL_DIR:PATH=C:/install/lib/cmake
+_Private_DIR:PATH=C:/private/lib/cmake
+Zstd_DIR:STRING=C:/zstd/lib/cmake/zstd
+"#;
+
+    fn cache() -> CmakeCache {
+        return CmakeCache::parse(CACHE, PathBuf::from("C:/build"));
+    }
+
+    #[test]
+    fn parses_cache_entries() {
+        let cache = cache();
+        assert_eq!(cache.get("CMAKE_BUILD_TYPE"), Some("RelWithDebInfo"));
+        assert_eq!(cache.get("CMAKE_CXX_COMPILER_TARGET"), None);
+        assert_eq!(cache.get("Boost_DIR"), None);
+        assert_eq!(cache.get("MISSING"), None);
+        assert_eq!(cache.build_config(), Some(BuildConfig::Release));
+        assert_eq!(
+            cache.target_triple().as_deref(),
+            Some("x86_64-w64-windows-gnu")
+        );
+        assert_eq!(
+            cache.runtime_output_dir(),
+            Some(PathBuf::from("C:/build/bin"))
+        );
+        assert_eq!(
+            cache.compiler(),
+            Some(normalize_separators(
+                r"C:\llvm-mingw\bin\x86_64-w64-mingw32-clang++.exe"
+            ))
+        );
+        assert_eq!(cache.compiler_dirs(), [PathBuf::from("C:/llvm-mingw/bin")]);
+
+        let debug = CmakeCache::parse("CMAKE_BUILD_TYPE:STRING=debug\n", PathBuf::new());
+        assert_eq!(debug.build_config(), Some(BuildConfig::Debug));
+        let multi_config = CmakeCache::parse("CMAKE_BUILD_TYPE:STRING=\n", PathBuf::new());
+        assert_eq!(multi_config.build_config(), None);
+    }
+
+    #[test]
+    fn finds_prefix_paths() {
+        assert_eq!(
+            cache().prefix_paths(),
+            [
+                "C:/deps",
+                "C:/qt/6.5.0/mingw_64",
+                "C:/vcpkg/installed/x64-mingw-dynamic",
+                "C:/fmt",
+            ]
+            .map(PathBuf::from)
+        );
+    }
+
+    #[test]
+    fn finds_package_prefixes() {
+        let prefix = |dir: &str| package_prefix(Path::new(dir));
+        assert_eq!(
+            prefix("/opt/qt/lib/cmake/Qt6"),
+            Some(PathBuf::from("/opt/qt"))
+        );
+        assert_eq!(
+            prefix("/opt/zlib/lib64/cmake/ZLIB"),
+            Some(PathBuf::from("/opt/zlib"))
+        );
+        assert_eq!(
+            prefix("/usr/lib/x86_64-linux-gnu/cmake/Foo"),
+            Some(PathBuf::from("/usr"))
+        );
+        assert_eq!(
+            prefix("/vcpkg/x64/share/zlib"),
+            Some(PathBuf::from("/vcpkg/x64"))
+        );
+        assert_eq!(prefix("/opt/fmt/CMake"), Some(PathBuf::from("/opt/fmt")));
+
+        let opencv = test_dir("cmake-opencv-prefix").join("build");
+        for dir in ["x64/vc15/bin", "x64/vc16/bin", "x64/vc16/lib", "include"] {
+            std::fs::create_dir_all(opencv.join(dir)).unwrap();
+        }
+        assert_eq!(package_prefix(&opencv), Some(opencv.join("x64/vc16")));
+        std::fs::create_dir_all(opencv.join("bin")).unwrap();
+        assert_eq!(package_prefix(&opencv), Some(opencv));
+    }
+
+    #[test]
+    fn loads_cache_of_build_dir() {
+        let build_dir = test_dir("cmake-cache");
+        std::fs::write(
+            build_dir.join(CMAKE_CACHE_FILE_NAME),
+            "CMAKE_BUILD_TYPE:STRING=Debug\n",
+        )
+        .unwrap();
+        let cache = CmakeCache::load(&build_dir).unwrap();
+        assert_eq!(cache.build_dir, build_dir);
+        assert_eq!(cache.build_config(), Some(BuildConfig::Debug));
+        let cache = CmakeCache::load(&build_dir.join(CMAKE_CACHE_FILE_NAME)).unwrap();
+        assert_eq!(cache.build_dir, build_dir);
+
+        let missing = CmakeCache::load(&build_dir.join("missing"));
+        assert!(matches!(missing, Err(Error::Io { .. })));
+    }
+}
@@ -0,0 +1,319 @@
+use crate::error::Error;
+use crate::resolver::ResolverKind;
+use crate::util::{is_file, long_path, normalize_separators, parse_size};
+use crate::validator::{FileVersion, ValidatorKind};
+use serde::{Deserialize, Deserializer};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Name of the config file, discovered next to the target binary or in its parent dirs.
+pub const CONFIG_FILE_NAME: &str = "deploy-dll.toml";
+
+/// Options of `deploy-dll` read from a `deploy-dll.toml`.
+///
+/// Keys are named like the command line options, for example `shallow-search-dir = ["lib"]`.
+/// Every key is optional, options given on the command line or by environment variables take
+/// precedence. `${VAR}` in strings is replaced by the environment variable `VAR`, and relative
+/// paths are relative to the dir of the config file.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ConfigFile {
+    #[serde(alias = "binary-file", deserialize_with = "one_or_many")]
+    pub binary_files: Option<Vec<PathBuf>>,
+    pub optional_dlls: Option<Vec<PathBuf>>,
+    pub skip_env_path: Option<bool>,
+    pub search_var: Option<Vec<String>>,
+    pub copy_vc_redist: Option<bool>,
+    pub vc_redist_dir: Option<PathBuf>,
+    pub ucrt_dir: Option<PathBuf>,
+    pub target_triple: Option<String>,
+    pub compiler: Option<PathBuf>,
+    pub cmake_cache: Option<PathBuf>,
+    pub verbose: Option<bool>,
+    pub shallow_search_dir: Option<Vec<PathBuf>>,
+    pub no_shallow_search: Option<bool>,
+    pub deep_search_dir: Option<Vec<PathBuf>>,
+    pub no_deep_search: Option<bool>,
+    pub cmake_prefix_path: Option<Vec<PathBuf>>,
+    pub skip_env_cmake_prefix_path: Option<bool>,
+    pub ignore: Option<Vec<String>>,
+    #[serde(deserialize_with = "from_str_list")]
+    pub resolver_order: Option<Vec<ResolverKind>>,
+    #[serde(deserialize_with = "from_str_list")]
+    pub enable_validator: Option<Vec<ValidatorKind>>,
+    #[serde(deserialize_with = "from_str_list")]
+    pub disable_validator: Option<Vec<ValidatorKind>>,
+    #[serde(deserialize_with = "from_str_opt")]
+    pub min_file_version: Option<FileVersion>,
+    pub allow_hash: Option<Vec<String>>,
+    pub allow_missing: Option<bool>,
+    pub allow_non_redistributable: Option<bool>,
+    pub deny_mixed_crt: Option<bool>,
+    pub audit_orphans: Option<bool>,
+    pub delete_orphans: Option<bool>,
+    pub manifest: Option<PathBuf>,
+    pub size_report: Option<bool>,
+    #[serde(deserialize_with = "size_opt")]
+    pub size_budget: Option<u64>,
+    pub import_report: Option<bool>,
+    pub sarif: Option<PathBuf>,
+    pub scan: Option<bool>,
+    pub include: Option<Vec<String>>,
+    pub exclude: Option<Vec<String>>,
+    /// Dll names mapped to the file deployed for them instead of searching
+    pub overrides: BTreeMap<String, PathBuf>,
+    /// The file this config is loaded from
+    #[serde(skip)]
+    pub path: PathBuf,
+}
+
+impl ConfigFile {
+    /// Read and parse a config file, expanding environment variables and relative paths.
+    pub fn load(path: &Path) -> Result<ConfigFile, Error> {
+        return ConfigFile::load_with_vars(path, &|name| std::env::var(name).ok());
+    }
+
+    /// Like [`ConfigFile::load`], but `${VAR}` is replaced by `vars("VAR")` instead of the
+    /// environment variable.
+    pub fn load_with_vars(
+        path: &Path,
+        vars: &dyn Fn(&str) -> Option<String>,
+    ) -> Result<ConfigFile, Error> {
+        let text = std::fs::read_to_string(long_path(path))
+            .map_err(|e| Error::io(format!("Failed to read \"{}\"", path.display()), e))?;
+        let table: toml::Table = text.parse().map_err(|e: toml::de::Error| Error::Parse {
+            path: path.to_path_buf(),
+            message: e.to_string().trim_end().to_string(),
+        })?;
+        let mut value = toml::Value::Table(table);
+        expand_value(&mut value, vars).map_err(|name| {
+            return Error::config(format!(
+                "Environment variable {name} used in \"{}\" is not set",
+                path.display()
+            ));
+        })?;
+        let mut config = ConfigFile::deserialize(value).map_err(|e| Error::Parse {
+            path: path.to_path_buf(),
+            message: e.message().to_string(),
+        })?;
+
+        let dir = path.parent().unwrap_or(Path::new(""));
+        let resolve = |p: &mut PathBuf| {
+            let normalized = normalize_separators(&*p);
+            *p = if normalized.is_relative() {
+                dir.join(normalized)
+            } else {
+                normalized
+            };
+        };
+        config.binary_files.iter_mut().flatten().for_each(resolve);
+        for dirs in [
+            &mut config.shallow_search_dir,
+            &mut config.deep_search_dir,
+            &mut config.cmake_prefix_path,
+        ] {
+            dirs.iter_mut().flatten().for_each(resolve);
+        }
+        config.vc_redist_dir.iter_mut().for_each(resolve);
+        config.ucrt_dir.iter_mut().for_each(resolve);
+        config.compiler.iter_mut().for_each(resolve);
+        config.cmake_cache.iter_mut().for_each(resolve);
+        config.manifest.iter_mut().for_each(resolve);
+        config.sarif.iter_mut().for_each(resolve);
+        config.overrides.values_mut().for_each(resolve);
+        config.path = path.to_path_buf();
+        return Ok(config);
+    }
+
+    /// Find [`CONFIG_FILE_NAME`] in `dir` or its parent dirs.
+    pub fn discover(dir: &Path) -> Option<PathBuf> {
+        return dir
+            .ancestors()
+            .map(|d| d.join(CONFIG_FILE_NAME))
+            .find(is_file);
+    }
+}
+
+/// Replace `${VAR}` with the value of environment variable `VAR`, and `$$` with `$`. Returns the
+/// name of the variable if it is not set.
+pub fn expand_env_vars(text: &str) -> Result<String, String> {
+    return expand_vars(text, &|name| std::env::var(name).ok());
+}
+
+/// Replace `${VAR}` with `vars("VAR")`, and `$$` with `$`. Returns the name of the variable if
+/// `vars` has no value for it.
+pub fn expand_vars(text: &str, vars: &dyn Fn(&str) -> Option<String>) -> Result<String, String> {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(pos) = rest.find('$') {
+        result.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        if let Some(after) = rest.strip_prefix("$$") {
+            result.push('$');
+            rest = after;
+            continue;
+        }
+        let end = match rest.strip_prefix("${").and_then(|r| r.find('}')) {
+            Some(end) => end,
+            None => {
+                result.push('$');
+                rest = &rest[1..];
+                continue;
+            }
+        };
+        let name = &rest[2..2 + end];
+        match vars(name) {
+            Some(value) => result.push_str(&value),
+            None => return Err(name.to_string()),
+        }
+        rest = &rest[3 + end..];
+    }
+    result.push_str(rest);
+    return Ok(result);
+}
+
+fn expand_value(
+    value: &mut toml::Value,
+    vars: &dyn Fn(&str) -> Option<String>,
+) -> Result<(), String> {
+    match value {
+        toml::Value::String(s) => *s = expand_vars(s, vars)?,
+        toml::Value::Array(items) => {
+            for item in items {
+                expand_value(item, vars)?;
+            }
+        }
+        toml::Value::Table(table) => {
+            for (_, item) in table.iter_mut() {
+                expand_value(item, vars)?;
+            }
+        }
+        _ => {}
+    }
+    return Ok(());
+}
+
+fn from_str_list<'de, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: std::str::FromStr<Err = String>,
+{
+    let items = Vec::<String>::deserialize(deserializer)?;
+    let parsed: Result<Vec<T>, String> = items.iter().map(|s| s.parse()).collect();
+    return parsed.map(Some).map_err(serde::de::Error::custom);
+}
+
+fn from_str_opt<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: std::str::FromStr<Err = String>,
+{
+    let text = String::deserialize(deserializer)?;
+    return text.parse().map(Some).map_err(serde::de::Error::custom);
+}
+
+/// A size is either a number of bytes or a string like `200M`
+fn size_opt<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Size {
+        Bytes(u64),
+        Text(String),
+    }
+    return match Size::deserialize(deserializer)? {
+        Size::Bytes(bytes) => Ok(Some(bytes)),
+        Size::Text(text) => parse_size(&text)
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+    };
+}
+
+/// A single path or a list of paths
+fn one_or_many<'de, D>(deserializer: D) -> Result<Option<Vec<PathBuf>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(PathBuf),
+        Many(Vec<PathBuf>),
+    }
+    return match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(path) => Ok(Some(vec![path])),
+        OneOrMany::Many(paths) => Ok(Some(paths)),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::test_dir;
+
+    /// Variables of the tests, instead of the environment of the process
+    fn vars(name: &str) -> Option<String> {
+        return match name {
+            "QT_DIR" => Some("/opt/qt".to_string()),
+            "DEPS_DIR" => Some("/opt/deps".to_string()),
+            _ => None,
+        };
+    }
+
+    #[test]
+    fn expands_env_vars() {
+        let expand = |text: &str| expand_vars(text, &vars);
+        assert_eq!(expand("${QT_DIR}/bin").as_deref(), Ok("/opt/qt/bin"));
+        assert_eq!(expand("$$HOME costs $5").as_deref(), Ok("$HOME costs $5"));
+        assert_eq!(expand("${unclosed").as_deref(), Ok("${unclosed"));
+        assert_eq!(expand("a${UNSET}b"), Err("UNSET".to_string()));
+    }
+
+    #[test]
+    fn loads_relative_paths_and_env_vars() {
+        let dir = test_dir("config-load");
+        let text = r#"
+binary-file = "bin/app.exe"
+shallow-search-dir = ["lib", "${DEPS_DIR}/bin"]
+size-budget = "2M"
+resolver-order = ["vcpkg", "shallow"]
+
+[overrides]
+"zlib1.dll" = "vendor/zlib1.dll"
+"#;
+        std::fs::write(dir.join(CONFIG_FILE_NAME), text).unwrap();
+        std::fs::create_dir_all(dir.join("bin")).unwrap();
+        assert_eq!(
+            ConfigFile::discover(&dir.join("bin")),
+            Some(dir.join(CONFIG_FILE_NAME))
+        );
+
+        let config = ConfigFile::load_with_vars(&dir.join(CONFIG_FILE_NAME), &vars).unwrap();
+        assert_eq!(config.binary_files, Some(vec![dir.join("bin/app.exe")]));
+        assert_eq!(
+            config.shallow_search_dir,
+            Some(vec![dir.join("lib"), PathBuf::from("/opt/deps/bin")])
+        );
+        assert_eq!(config.size_budget, Some(2 << 20));
+        assert_eq!(
+            config.resolver_order,
+            Some(vec![ResolverKind::Vcpkg, ResolverKind::Shallow])
+        );
+        assert_eq!(config.overrides["zlib1.dll"], dir.join("vendor/zlib1.dll"));
+        assert_eq!(config.verbose, None);
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_unset_vars() {
+        let dir = test_dir("config-errors");
+        let path = dir.join(CONFIG_FILE_NAME);
+        std::fs::write(&path, "shallow-search-dirs = [\"lib\"]\n").unwrap();
+        let load = |path: &Path| ConfigFile::load_with_vars(path, &vars);
+        assert!(matches!(load(&path), Err(Error::Parse { .. })));
+
+        std::fs::write(&path, "compiler = \"${UNSET}/gcc\"\n").unwrap();
+        assert!(matches!(load(&path), Err(Error::Config { .. })));
+    }
+}
@@ -0,0 +1,295 @@
+use crate::msvc::{classify_runtime_dll, RuntimeComponent};
+use crate::pe::get_dependencies;
+use crate::resolver::BuildConfig;
+use crate::util::display_filename;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+/// The C runtime a module is linked against. Heap memory, `FILE` handles and C++ objects must not
+/// be passed between modules of different C runtimes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CrtFlavor {
+    /// `msvcrt.dll` shipped with Windows, used by MinGW
+    Msvcrt,
+    /// The UCRT without the VC runtime, used by MinGW-UCRT and llvm-mingw
+    MingwUcrt { debug: bool },
+    /// The VC runtime of a Visual Studio toolset, like `vcruntime140.dll` or `msvcr120.dll`
+    Msvc { version: u32, debug: bool },
+}
+
+impl CrtFlavor {
+    /// Debug C runtimes are only imported by debug builds
+    pub fn build_config(self) -> BuildConfig {
+        return match self {
+            CrtFlavor::MingwUcrt { debug: true } | CrtFlavor::Msvc { debug: true, .. } => {
+                BuildConfig::Debug
+            }
+            _ => BuildConfig::Release,
+        };
+    }
+}
+
+impl std::fmt::Display for CrtFlavor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            CrtFlavor::Msvcrt => write!(f, "msvcrt"),
+            CrtFlavor::MingwUcrt { debug: false } => write!(f, "UCRT"),
+            CrtFlavor::MingwUcrt { debug: true } => write!(f, "debug UCRT"),
+            CrtFlavor::Msvc {
+                version,
+                debug: false,
+            } => write!(f, "MSVC runtime {version}"),
+            CrtFlavor::Msvc {
+                version,
+                debug: true,
+            } => write!(f, "debug MSVC runtime {version}"),
+        };
+    }
+}
+
+/// Classify the C runtime of a module by the names of the dlls it imports, `None` if it imports
+/// no C runtime.
+pub fn classify_crt<'a>(imports: impl IntoIterator<Item = &'a str>) -> Option<CrtFlavor> {
+    let mut msvc: Option<(u32, bool)> = None;
+    let mut ucrt: Option<bool> = None;
+    let mut msvcrt = false;
+    for name in imports {
+        let dll = match classify_runtime_dll(name) {
+            Some(dll) => dll,
+            None => continue,
+        };
+        match dll.component {
+            RuntimeComponent::VcRuntime
+            | RuntimeComponent::StdCpp
+            | RuntimeComponent::LegacyCrt => {
+                if let Some(version) = dll.version {
+                    let debug = msvc.is_some_and(|(_, debug)| debug) || dll.debug;
+                    msvc = Some((msvc.map_or(version, |(v, _)| v), debug));
+                }
+            }
+            RuntimeComponent::Ucrt | RuntimeComponent::UcrtForwarder => {
+                ucrt = Some(ucrt.unwrap_or(false) || dll.debug);
+            }
+            RuntimeComponent::SystemCrt => msvcrt = true,
+            _ => {}
+        }
+    }
+    if let Some((version, debug)) = msvc {
+        return Some(CrtFlavor::Msvc { version, debug });
+    }
+    if let Some(debug) = ucrt {
+        return Some(CrtFlavor::MingwUcrt { debug });
+    }
+    return msvcrt.then_some(CrtFlavor::Msvcrt);
+}
+
+/// Classify the C runtime of a dll or exe, `None` if it can't be read or imports no C runtime.
+pub fn crt_of_file(file: &Path) -> Option<CrtFlavor> {
+    let deps = get_dependencies(file, false).ok()?;
+    return classify_crt(deps.iter().map(|d| d.dll_name.as_str()));
+}
+
+/// A module whose C runtime differs from the one of the binary it is deployed for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrtConflict {
+    pub root: PathBuf,
+    pub root_crt: CrtFlavor,
+    pub module: PathBuf,
+    pub module_crt: CrtFlavor,
+    /// Imports as `(importer, dll)` where objects may cross between C runtimes
+    pub boundaries: Vec<(PathBuf, PathBuf)>,
+}
+
+impl std::fmt::Display for CrtConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let boundaries: Vec<String> = self
+            .boundaries
+            .iter()
+            .map(|(importer, dll)| {
+                return format!(
+                    "{} -> {}",
+                    display_filename(importer),
+                    display_filename(dll)
+                );
+            })
+            .collect();
+        return write!(
+            f,
+            "\"{}\" uses {}, but \"{}\" uses {}, objects may cross C runtimes at {}",
+            self.module.display(),
+            self.module_crt,
+            self.root.display(),
+            self.root_crt,
+            boundaries.join(", ")
+        );
+    }
+}
+
+/// Modules and the imports between them, with the C runtime of every module.
+#[derive(Debug, Clone, Default)]
+pub struct ModuleGraph {
+    crts: HashMap<PathBuf, Option<CrtFlavor>>,
+    /// `(importer, dll)` in the order they are found
+    edges: Vec<(PathBuf, PathBuf)>,
+}
+
+impl ModuleGraph {
+    pub fn add_module(&mut self, module: &Path, crt: Option<CrtFlavor>) {
+        self.crts.insert(module.to_path_buf(), crt);
+    }
+
+    pub fn add_edge(&mut self, importer: &Path, dll: &Path) {
+        let edge = (importer.to_path_buf(), dll.to_path_buf());
+        if !self.edges.contains(&edge) {
+            self.edges.push(edge);
+        }
+    }
+
+    pub fn crt(&self, module: &Path) -> Option<CrtFlavor> {
+        return self.crts.get(module).copied().flatten();
+    }
+
+    /// Modules reachable from `root` whose C runtime differs from the one of `root`. Modules
+    /// without a C runtime don't conflict with anything.
+    pub fn crt_conflicts(&self, root: &Path) -> Vec<CrtConflict> {
+        let root_crt = match self.crt(root) {
+            Some(crt) => crt,
+            None => return Vec::new(),
+        };
+        let mut reachable: Vec<&Path> = Vec::new();
+        let mut visited: HashSet<&Path> = HashSet::new();
+        let mut pending: VecDeque<&Path> = VecDeque::from([root]);
+        while let Some(module) = pending.pop_front() {
+            if !visited.insert(module) {
+                continue;
+            }
+            reachable.push(module);
+            for (importer, dll) in &self.edges {
+                if importer == module {
+                    pending.push_back(dll);
+                }
+            }
+        }
+
+        let mut conflicts = Vec::new();
+        for module in reachable.into_iter().skip(1) {
+            let module_crt = match self.crt(module) {
+                Some(crt) if crt != root_crt => crt,
+                _ => continue,
+            };
+            let crosses = |(importer, dll): &&(PathBuf, PathBuf)| {
+                return (importer == module || dll == module)
+                    && matches!(
+                        (self.crt(importer), self.crt(dll)),
+                        (Some(a), Some(b)) if a != b
+                    );
+            };
+            let mut boundaries: Vec<(PathBuf, PathBuf)> =
+                self.edges.iter().filter(crosses).cloned().collect();
+            if boundaries.is_empty() {
+                // Reached through modules without a C runtime
+                boundaries = self
+                    .edges
+                    .iter()
+                    .filter(|(_, dll)| dll == module)
+                    .cloned()
+                    .collect();
+            }
+            conflicts.push(CrtConflict {
+                root: root.to_path_buf(),
+                root_crt,
+                module: module.to_path_buf(),
+                module_crt,
+                boundaries,
+            });
+        }
+        return conflicts;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_crts() {
+        assert_eq!(
+            classify_crt(["KERNEL32.dll", "msvcrt.dll"]),
+            Some(CrtFlavor::Msvcrt)
+        );
+        assert_eq!(
+            classify_crt(["api-ms-win-crt-runtime-l1-1-0.dll", "kernel32.dll"]),
+            Some(CrtFlavor::MingwUcrt { debug: false })
+        );
+        assert_eq!(
+            classify_crt(["ucrtbased.dll"]),
+            Some(CrtFlavor::MingwUcrt { debug: true })
+        );
+        assert_eq!(
+            classify_crt(["api-ms-win-crt-heap-l1-1-0.dll", "VCRUNTIME140.dll"]),
+            Some(CrtFlavor::Msvc {
+                version: 140,
+                debug: false
+            })
+        );
+        assert_eq!(
+            classify_crt(["msvcp140d.dll", "vcruntime140d.dll", "ucrtbased.dll"]),
+            Some(CrtFlavor::Msvc {
+                version: 140,
+                debug: true
+            })
+        );
+        assert_eq!(
+            classify_crt(["msvcr120.dll"]),
+            Some(CrtFlavor::Msvc {
+                version: 120,
+                debug: false
+            })
+        );
+        assert_eq!(classify_crt(["kernel32.dll", "user32.dll"]), None);
+        assert_eq!(classify_crt([]), None);
+    }
+
+    #[test]
+    fn finds_crt_conflicts() {
+        let msvc = CrtFlavor::Msvc {
+            version: 140,
+            debug: false,
+        };
+        let (app, qt, zlib, gcc) = (
+            Path::new("app.exe"),
+            Path::new("qt.dll"),
+            Path::new("zlib1.dll"),
+            Path::new("libgcc.dll"),
+        );
+        let mut graph = ModuleGraph::default();
+        graph.add_module(app, Some(msvc));
+        graph.add_module(qt, None);
+        graph.add_module(zlib, Some(CrtFlavor::Msvcrt));
+        graph.add_module(gcc, Some(CrtFlavor::Msvcrt));
+        graph.add_edge(app, qt);
+        graph.add_edge(qt, zlib);
+        graph.add_edge(app, gcc);
+        graph.add_edge(app, gcc);
+        graph.add_edge(zlib, gcc);
+
+        let conflicts = graph.crt_conflicts(app);
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(conflicts[0].module, gcc);
+        assert_eq!(conflicts[0].module_crt, CrtFlavor::Msvcrt);
+        assert_eq!(conflicts[0].root_crt, msvc);
+        assert_eq!(
+            conflicts[0].boundaries,
+            [(app.to_path_buf(), gcc.to_path_buf())]
+        );
+        // Reached through qt.dll, which has no C runtime
+        assert_eq!(conflicts[1].module, zlib);
+        assert_eq!(
+            conflicts[1].boundaries,
+            [(qt.to_path_buf(), zlib.to_path_buf())]
+        );
+
+        assert!(graph.crt_conflicts(zlib).is_empty());
+        assert!(graph.crt_conflicts(qt).is_empty());
+    }
+}
@@ -0,0 +1,1554 @@
+use crate::cmake::CmakeCache;
+use crate::crt::{classify_crt, crt_of_file, CrtConflict, CrtFlavor, ModuleGraph};
+use crate::diagnostic::{Diagnostic, DiagnosticKind};
+use crate::error::Error;
+use crate::manifest::sha256_hex;
+use crate::msvc::{classify_runtime_dll, runtime_family, ucrt_redist_dir, ucrt_redist_dlls};
+use crate::pe::{get_dependencies, get_linker_version, get_machine, machine_name, Dependency};
+use crate::resolver::{
+    BuildConfig, CmakePrefixResolver, DeepDirResolver, DirResolver, DllValidator, Resolver,
+    ResolverKind, VcRedistResolver, VcpkgResolver,
+};
+use crate::search::existing_search_var;
+use crate::system::{
+    is_debug_crt_dll, is_system_dll, is_ucrt_dll, is_vc_redist_dll, non_redistributable_reason,
+};
+use crate::util::{can_be_dir, is_file, long_path, normalize_separators};
+use crate::validator::{
+    ExportsValidator, FileVersion, HasSignatureValidator, HashAllowlistValidator, MachineValidator,
+    MinFileVersionValidator, PeValidator, RejectedCandidate, RuntimeVersionValidator,
+    ValidationRequest, Validator, ValidatorKind, ValidatorPipeline,
+};
+use crate::vcpkg::{path_key, VcpkgPrefix};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Environment variables searched for dlls by default, useful for cross compiling on a Linux host.
+pub const DEFAULT_SEARCH_VARS: [&str; 2] = ["DEPLOY_DLL_PATH", "WINEPATH"];
+
+/// Recorded as the resolver of dlls deployed from the UCRT redistributable.
+const UCRT_RESOLVER: &str = "ucrt";
+
+/// What to do with Microsoft Visual C/C++ redistributable dlls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VcRedistPolicy {
+    /// Don't deploy them, users should install VC redistributable
+    #[default]
+    Skip,
+    /// Search and copy them like other dlls
+    Copy,
+}
+
+/// A dll that will be copied into the output dir.
+#[derive(Debug, Clone)]
+pub struct PlannedDll {
+    /// Lower case filename of the dll
+    pub name: String,
+    pub source: PathBuf,
+    pub destination: PathBuf,
+    /// The first binary found to require this dll
+    pub required_by: PathBuf,
+    /// Name of the resolver that found the dll
+    pub resolver: String,
+    /// The vcpkg port that installs the dll
+    pub port: Option<String>,
+}
+
+/// A dll that can't be found.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MissingDll {
+    /// Lower case filename of the dll
+    pub name: String,
+    pub required_by: PathBuf,
+}
+
+/// A deployed dll that must not be shipped, like the debug C runtime.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NonRedistributableDll {
+    /// Lower case filename of the dll
+    pub name: String,
+    pub reason: String,
+    pub required_by: PathBuf,
+}
+
+/// Dlls to deploy, computed by [`Deployer::plan`]. Nothing is copied until
+/// [`Deployer::execute`] is called.
+#[derive(Debug, Clone, Default)]
+pub struct DeploymentPlan {
+    pub output_dir: PathBuf,
+    /// Dlls to copy, in the order they are found
+    pub dlls: Vec<PlannedDll>,
+    pub missing: Vec<MissingDll>,
+    /// Dlls in the plan that must not be shipped
+    pub non_redistributable: Vec<NonRedistributableDll>,
+    /// Dlls whose C runtime differs from the one of a target
+    pub crt_conflicts: Vec<CrtConflict>,
+    /// Candidates skipped because a validator rejected them
+    pub rejections: Vec<RejectedCandidate>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Targets whose dlls are deployed into the same dir, planned by [`Deployer::plan_groups`].
+#[derive(Debug, Clone, Default)]
+pub struct TargetGroup {
+    pub output_dir: PathBuf,
+    pub targets: Vec<PathBuf>,
+}
+
+/// Properties of an importer that decide which file is chosen for a dll.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Importer {
+    machine: Option<u16>,
+    linker_version: Option<(u8, u8)>,
+    crt: Option<CrtFlavor>,
+}
+
+/// Found dlls by lower case name and the importer, shared by target groups.
+type ResolutionCache = HashMap<(String, Importer), (PathBuf, String)>;
+
+/// Unvalidated candidates of every resolver by lower case name, config and machine, to find
+/// ambiguous dlls without searching again.
+type CandidateCache = HashMap<(String, Option<BuildConfig>, Option<u16>), Vec<Vec<PathBuf>>>;
+
+/// Resolvers, validators and the dlls found by them, built once for all target groups.
+struct SearchState {
+    resolvers: Vec<Arc<dyn Resolver>>,
+    validators: ValidatorPipeline,
+    /// Owning vcpkg ports of files, keyed by [`path_key`]
+    ports: HashMap<String, String>,
+    found: ResolutionCache,
+    candidates: RefCell<CandidateCache>,
+}
+
+/// Resolves and deploys dlls for exes or dlls.
+///
+/// ```no_run
+/// use shared_lib_deployer::{Deployer, VcRedistPolicy};
+/// use std::path::PathBuf;
+///
+/// let plan = Deployer::new()
+///     .shallow_search_dir("C:/Qt/6.6.2/msvc2019_64/bin")
+///     .cmake_prefix_path("C:/vcpkg/installed/x64-windows")
+///     .ignore("opengl32sw.dll")
+///     .vc_redist(VcRedistPolicy::Copy)
+///     .deploy(&[PathBuf::from("install/bin/app.exe")])
+///     .unwrap();
+/// for dll in &plan.dlls {
+///     println!("{} -> {}", dll.source.display(), dll.destination.display());
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Deployer {
+    shallow_search_dirs: Vec<PathBuf>,
+    deep_search_dirs: Vec<PathBuf>,
+    build_dirs: Vec<PathBuf>,
+    cmake_prefix_paths: Vec<PathBuf>,
+    shallow_search: bool,
+    deep_search: bool,
+    search_env_path: bool,
+    search_env_cmake_prefix_path: bool,
+    ignore: Vec<String>,
+    vc_redist: VcRedistPolicy,
+    allow_missing: bool,
+    allow_non_redistributable: bool,
+    deny_mixed_crt: bool,
+    verbose: bool,
+    output_dir: Option<PathBuf>,
+    resolver_order: Vec<ResolverKind>,
+    custom_resolvers: Vec<Arc<dyn Resolver>>,
+    search_vars: Vec<String>,
+    vc_redist_dir: Option<PathBuf>,
+    ucrt_dir: Option<PathBuf>,
+    target_triple: Option<String>,
+    compiler: Option<PathBuf>,
+    build_config: Option<BuildConfig>,
+    overrides: BTreeMap<String, PathBuf>,
+    validators: Vec<ValidatorKind>,
+    min_file_version: Option<FileVersion>,
+    hash_allowlist: HashSet<String>,
+    custom_validators: Vec<Arc<dyn Validator>>,
+}
+
+impl Default for Deployer {
+    fn default() -> Self {
+        return Deployer::new();
+    }
+}
+
+impl Deployer {
+    pub fn new() -> Deployer {
+        return Deployer {
+            shallow_search_dirs: Vec::new(),
+            deep_search_dirs: Vec::new(),
+            build_dirs: Vec::new(),
+            cmake_prefix_paths: Vec::new(),
+            shallow_search: true,
+            deep_search: true,
+            search_env_path: true,
+            search_env_cmake_prefix_path: true,
+            ignore: Vec::new(),
+            vc_redist: VcRedistPolicy::Skip,
+            allow_missing: false,
+            allow_non_redistributable: false,
+            deny_mixed_crt: false,
+            verbose: false,
+            output_dir: None,
+            resolver_order: ResolverKind::DEFAULT_ORDER.to_vec(),
+            custom_resolvers: Vec::new(),
+            search_vars: DEFAULT_SEARCH_VARS.iter().map(|v| v.to_string()).collect(),
+            vc_redist_dir: None,
+            ucrt_dir: None,
+            target_triple: None,
+            compiler: None,
+            build_config: None,
+            overrides: BTreeMap::new(),
+            validators: ValidatorKind::DEFAULT.to_vec(),
+            min_file_version: None,
+            hash_allowlist: HashSet::new(),
+            custom_validators: Vec::new(),
+        };
+    }
+
+    /// Search for dll in this dir
+    pub fn shallow_search_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.shallow_search_dirs
+            .push(normalize_separators(dir.into()));
+        return self;
+    }
+
+    /// Search for dll recursively in this dir
+    pub fn deep_search_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.deep_search_dirs.push(normalize_separators(dir.into()));
+        return self;
+    }
+
+    /// Search for dll recursively in this build dir after every other resolver, so that dlls
+    /// found elsewhere for the config of the importer are preferred
+    pub fn build_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        let dir = normalize_separators(dir.into());
+        if !self.build_dirs.contains(&dir) {
+            self.build_dirs.push(dir);
+        }
+        return self;
+    }
+
+    /// A prefix in CMAKE_PREFIX_PATH, dlls are searched at the locations of imported targets
+    /// exported by packages in it, and in `bin`, `bin/<config>`, `lib` and `<arch>/bin`
+    pub fn cmake_prefix_path(mut self, prefix: impl Into<PathBuf>) -> Self {
+        self.cmake_prefix_paths
+            .push(normalize_separators(prefix.into()));
+        return self;
+    }
+
+    /// Enable or disable shallow search, enabled by default
+    pub fn shallow_search(mut self, enable: bool) -> Self {
+        self.shallow_search = enable;
+        return self;
+    }
+
+    /// Enable or disable recursive search of deep search dirs and build dirs, enabled by default
+    pub fn deep_search(mut self, enable: bool) -> Self {
+        self.deep_search = enable;
+        return self;
+    }
+
+    /// Search in system variable PATH on Windows, enabled by default
+    pub fn search_env_path(mut self, enable: bool) -> Self {
+        self.search_env_path = enable;
+        return self;
+    }
+
+    /// Also use prefixes in environment variable CMAKE_PREFIX_PATH, enabled by default
+    pub fn search_env_cmake_prefix_path(mut self, enable: bool) -> Self {
+        self.search_env_cmake_prefix_path = enable;
+        return self;
+    }
+
+    /// Dll file that won't be deployed
+    pub fn ignore(mut self, dll_name: impl Into<String>) -> Self {
+        self.ignore.push(dll_name.into());
+        return self;
+    }
+
+    pub fn vc_redist(mut self, policy: VcRedistPolicy) -> Self {
+        self.vc_redist = policy;
+        return self;
+    }
+
+    /// Don't fail [`Deployer::deploy`] if one or more dll failed to be found
+    pub fn allow_missing(mut self, allow: bool) -> Self {
+        self.allow_missing = allow;
+        return self;
+    }
+
+    /// Deploy dlls that must not be shipped like the debug C runtime, for example into a build
+    /// dir. Otherwise [`Deployer::deploy`] fails if it finds one
+    pub fn allow_non_redistributable(mut self, allow: bool) -> Self {
+        self.allow_non_redistributable = allow;
+        return self;
+    }
+
+    /// Fail [`Deployer::deploy`] if a dll uses another C runtime than the target it is deployed
+    /// for, instead of only reporting it
+    pub fn deny_mixed_crt(mut self, deny: bool) -> Self {
+        self.deny_mixed_crt = deny;
+        return self;
+    }
+
+    /// Show verbose information during execution
+    pub fn verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        return self;
+    }
+
+    /// Where dlls are copied to, defaults to the dir of the first target
+    pub fn output_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.output_dir = Some(normalize_separators(dir.into()));
+        return self;
+    }
+
+    /// Add a resolver, it is tried where [`ResolverKind::Custom`] is in the resolver order
+    pub fn resolver(mut self, resolver: impl Resolver + 'static) -> Self {
+        self.custom_resolvers.push(Arc::new(resolver));
+        return self;
+    }
+
+    /// The order to try resolvers, resolvers not listed are disabled.
+    /// Defaults to [`ResolverKind::DEFAULT_ORDER`]
+    pub fn resolver_order(mut self, order: &[ResolverKind]) -> Self {
+        self.resolver_order = order.to_vec();
+        return self;
+    }
+
+    /// PATH-like environment variables to search for dlls in, replacing
+    /// [`DEFAULT_SEARCH_VARS`]
+    pub fn search_vars(mut self, names: &[String]) -> Self {
+        self.search_vars = names.to_vec();
+        return self;
+    }
+
+    /// Copy VC redistributable dlls from the layout of Visual Studio, like `VC/Redist/MSVC` or
+    /// `VC/Redist/MSVC/<version>`. Defaults to environment variable `VCToolsRedistDir`
+    pub fn vc_redist_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.vc_redist_dir = Some(normalize_separators(dir.into()));
+        return self;
+    }
+
+    /// Deploy the UCRT app-locally from the layout of the Windows SDK, like
+    /// `Windows Kits/10/Redist/ucrt/DLLs`. When a binary imports `ucrtbase.dll` or an
+    /// `api-ms-win-crt-*` forwarder, every dll in the dir of its architecture is deployed, even if
+    /// other VC redistributable dlls are skipped
+    pub fn ucrt_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.ucrt_dir = Some(normalize_separators(dir.into()));
+        return self;
+    }
+
+    /// Search for the runtime dlls of the MinGW toolchain of this target triple, like
+    /// `x86_64-w64-mingw32`
+    pub fn target_triple(mut self, triple: impl Into<String>) -> Self {
+        self.target_triple = Some(triple.into());
+        return self;
+    }
+
+    /// Search for the runtime dlls of the MinGW, llvm-mingw or MSYS2 toolchain of this compiler,
+    /// the target triple is taken from its name if not given
+    pub fn compiler(mut self, compiler: impl Into<PathBuf>) -> Self {
+        self.compiler = Some(normalize_separators(compiler.into()));
+        return self;
+    }
+
+    /// Build config of the targets. Binaries that import msvcrt, like the ones built by MinGW,
+    /// are taken as builds of this config, since their C runtime can't tell it.
+    pub fn build_config(mut self, config: BuildConfig) -> Self {
+        self.build_config = Some(config);
+        return self;
+    }
+
+    /// Configure the search from the CMake cache of the build dir of the targets: the compilers
+    /// and their dirs, `CMAKE_RUNTIME_OUTPUT_DIRECTORY`, the target triple, the build type,
+    /// prefixes in `CMAKE_PREFIX_PATH`, of vcpkg and of packages found by `find_package`, and the
+    /// build dir, searched recursively as a fallback by [`ResolverKind::BuildDir`]. Options
+    /// already given are kept.
+    pub fn cmake_cache(mut self, cache: &CmakeCache) -> Self {
+        if self.verbose {
+            println!("Reading CMake cache of \"{}\"", cache.build_dir.display());
+        }
+        if let Some(dir) = cache.runtime_output_dir() {
+            self = self.shallow_search_dir(dir);
+        }
+        for dir in cache.compiler_dirs() {
+            self = self.shallow_search_dir(dir);
+        }
+        if self.compiler.is_none() {
+            self.compiler = cache.compiler();
+        }
+        if self.target_triple.is_none() {
+            self.target_triple = cache.target_triple();
+        }
+        if self.build_config.is_none() {
+            self.build_config = cache.build_config();
+        }
+        for prefix in cache.prefix_paths() {
+            if !self.cmake_prefix_paths.contains(&prefix) {
+                self.cmake_prefix_paths.push(prefix);
+            }
+        }
+        return self.build_dir(&cache.build_dir);
+    }
+
+    /// Deploy `source` for dlls named `dll_name` instead of searching for it
+    pub fn override_dll(mut self, dll_name: &str, source: impl Into<PathBuf>) -> Self {
+        self.overrides
+            .insert(dll_name.to_lowercase(), normalize_separators(source.into()));
+        return self;
+    }
+
+    /// Enable a built-in validator, [`ValidatorKind::DEFAULT`] are enabled by default
+    pub fn enable_validator(mut self, kind: ValidatorKind) -> Self {
+        if !self.validators.contains(&kind) {
+            self.validators.push(kind);
+        }
+        return self;
+    }
+
+    pub fn disable_validator(mut self, kind: ValidatorKind) -> Self {
+        self.validators.retain(|k| *k != kind);
+        return self;
+    }
+
+    /// Reject dlls whose file version is lower than `version`, enables the file version validator
+    pub fn min_file_version(mut self, version: FileVersion) -> Self {
+        self.min_file_version = Some(version);
+        return self.enable_validator(ValidatorKind::FileVersion);
+    }
+
+    /// Accept dlls with this sha256, enables the hash allowlist validator
+    pub fn allow_hash(mut self, sha256: impl Into<String>) -> Self {
+        self.hash_allowlist.insert(sha256.into().to_lowercase());
+        return self.enable_validator(ValidatorKind::Hash);
+    }
+
+    /// Add a validator, it runs after the built-in validators
+    pub fn validator(mut self, validator: impl Validator + 'static) -> Self {
+        self.custom_validators.push(Arc::new(validator));
+        return self;
+    }
+
+    /// Enabled validators in the order they run
+    pub fn validators(&self) -> ValidatorPipeline {
+        let mut validators: Vec<Arc<dyn Validator>> = Vec::new();
+        for kind in ValidatorKind::ALL {
+            if !self.validators.contains(&kind) {
+                continue;
+            }
+            match kind {
+                ValidatorKind::Pe => validators.push(Arc::new(PeValidator)),
+                ValidatorKind::Machine => validators.push(Arc::new(MachineValidator)),
+                ValidatorKind::FileVersion => {
+                    if let Some(minimum) = self.min_file_version {
+                        validators.push(Arc::new(MinFileVersionValidator { minimum }));
+                    }
+                }
+                ValidatorKind::Exports => validators.push(Arc::new(ExportsValidator)),
+                ValidatorKind::RuntimeVersion => validators.push(Arc::new(RuntimeVersionValidator)),
+                ValidatorKind::HasSignature => validators.push(Arc::new(HasSignatureValidator)),
+                ValidatorKind::Hash => validators.push(Arc::new(HashAllowlistValidator {
+                    allowed: self.hash_allowlist.clone(),
+                })),
+            }
+        }
+        validators.extend(self.custom_validators.iter().cloned());
+        return ValidatorPipeline::new(validators);
+    }
+
+    /// Enabled resolvers in the order they are tried
+    pub fn resolvers(&self) -> Vec<Arc<dyn Resolver>> {
+        let mut resolvers: Vec<Arc<dyn Resolver>> = Vec::new();
+        let mut added = HashSet::new();
+        for kind in &self.resolver_order {
+            if !added.insert(*kind) {
+                continue;
+            }
+            match kind {
+                ResolverKind::VcRedist => {
+                    let dir = match &self.vc_redist_dir {
+                        Some(dir) => Some(dir.clone()),
+                        None => std::env::var_os("VCToolsRedistDir").map(normalize_separators),
+                    };
+                    if let (VcRedistPolicy::Copy, Some(dir)) = (self.vc_redist, dir) {
+                        resolvers.push(Arc::new(VcRedistResolver::new(&dir, self.verbose)));
+                    }
+                }
+                ResolverKind::Shallow => {
+                    if self.shallow_search {
+                        resolvers.push(Arc::new(DirResolver::new(
+                            kind.name(),
+                            self.shallow_search_dirs.clone(),
+                            self.verbose,
+                        )));
+                    }
+                }
+                ResolverKind::Toolchain => {
+                    if self.target_triple.is_some() || self.compiler.is_some() {
+                        resolvers.push(Arc::new(DirResolver::toolchain(
+                            self.target_triple.as_deref(),
+                            self.compiler.as_deref(),
+                            self.verbose,
+                        )));
+                    }
+                }
+                ResolverKind::Vcpkg => {
+                    let prefixes = self.vcpkg_prefixes();
+                    if !prefixes.is_empty() {
+                        resolvers.push(Arc::new(VcpkgResolver::new(&prefixes, self.verbose)));
+                    }
+                }
+                ResolverKind::CmakePrefix => {
+                    // Prefixes of vcpkg are searched by the vcpkg resolver if it is enabled
+                    let prefixes: Vec<PathBuf> = self
+                        .cmake_prefixes()
+                        .iter()
+                        .filter(|p| {
+                            return !self.resolver_order.contains(&ResolverKind::Vcpkg)
+                                || VcpkgPrefix::detect(p).is_none();
+                        })
+                        .cloned()
+                        .collect();
+                    resolvers.push(Arc::new(CmakePrefixResolver::new(&prefixes, self.verbose)));
+                }
+                ResolverKind::EnvPath => {
+                    if cfg!(target_os = "windows") && self.search_env_path {
+                        resolvers.push(Arc::new(DirResolver::env_path(self.verbose)));
+                    }
+                }
+                ResolverKind::EnvVar => {
+                    resolvers.push(Arc::new(DirResolver::env_vars(
+                        &self.search_vars,
+                        self.verbose,
+                    )));
+                }
+                ResolverKind::Deep => {
+                    if self.deep_search {
+                        resolvers.push(Arc::new(DeepDirResolver::new(
+                            self.deep_search_dirs.clone(),
+                            self.verbose,
+                        )));
+                    }
+                }
+                ResolverKind::BuildDir => {
+                    if self.deep_search && !self.build_dirs.is_empty() {
+                        resolvers.push(Arc::new(DeepDirResolver::build_dirs(
+                            self.build_dirs.clone(),
+                            self.verbose,
+                        )));
+                    }
+                }
+                ResolverKind::Custom => {
+                    resolvers.extend(self.custom_resolvers.iter().cloned());
+                }
+            }
+        }
+        return resolvers;
+    }
+
+    /// Given CMake prefixes, followed by the ones in environment variable CMAKE_PREFIX_PATH if
+    /// enabled
+    pub fn cmake_prefixes(&self) -> Vec<PathBuf> {
+        let mut prefixes = self.cmake_prefix_paths.clone();
+        if self.search_env_cmake_prefix_path {
+            existing_search_var("CMAKE_PREFIX_PATH", &mut prefixes);
+        }
+        return prefixes;
+    }
+
+    /// CMake prefixes installed by vcpkg
+    pub fn vcpkg_prefixes(&self) -> Vec<VcpkgPrefix> {
+        return self
+            .cmake_prefixes()
+            .iter()
+            .filter_map(|p| VcpkgPrefix::detect(p))
+            .collect();
+    }
+
+    /// Find every dll required by `targets` recursively, without copying anything. Fails if a
+    /// target or a found dll is not a valid PE file.
+    pub fn plan(&self, targets: &[PathBuf]) -> Result<DeploymentPlan, Error> {
+        let targets: Vec<PathBuf> = targets.iter().map(normalize_separators).collect();
+        let output_dir = match &self.output_dir {
+            Some(dir) => dir.clone(),
+            None => targets
+                .first()
+                .and_then(|t| t.parent())
+                .map(Path::to_path_buf)
+                .unwrap_or_default(),
+        };
+        return self.plan_group(&targets, output_dir, &mut self.search_state());
+    }
+
+    /// Plan every group into its own output dir. Dlls found for one group are reused by the
+    /// others instead of searching again.
+    pub fn plan_groups(&self, groups: &[TargetGroup]) -> Result<Vec<DeploymentPlan>, Error> {
+        let mut state = self.search_state();
+        let mut plans = Vec::with_capacity(groups.len());
+        for group in groups {
+            let targets: Vec<PathBuf> = group.targets.iter().map(normalize_separators).collect();
+            let output_dir = normalize_separators(&group.output_dir);
+            plans.push(self.plan_group(&targets, output_dir, &mut state)?);
+        }
+        return Ok(plans);
+    }
+
+    fn search_state(&self) -> SearchState {
+        return SearchState {
+            resolvers: self.resolvers(),
+            validators: self.validators(),
+            ports: self
+                .vcpkg_prefixes()
+                .iter()
+                .flat_map(VcpkgPrefix::owners)
+                .collect(),
+            found: ResolutionCache::new(),
+            candidates: RefCell::new(CandidateCache::new()),
+        };
+    }
+
+    fn plan_group(
+        &self,
+        targets: &[PathBuf],
+        output_dir: PathBuf,
+        state: &mut SearchState,
+    ) -> Result<DeploymentPlan, Error> {
+        for (dll_name, source) in &self.overrides {
+            if !is_file(source) {
+                return Err(Error::config(format!(
+                    "Override \"{}\" of dll {dll_name} is not a file",
+                    source.display()
+                )));
+            }
+        }
+        if let Some(dir) = &self.ucrt_dir {
+            if !can_be_dir(dir) {
+                return Err(Error::config(format!(
+                    "UCRT redistributable dir \"{}\" is not a dir",
+                    dir.display()
+                )));
+            }
+        }
+        let mut context = Context {
+            deployer: self,
+            state,
+            visited: HashSet::new(),
+            graph: ModuleGraph::default(),
+            plan: DeploymentPlan {
+                output_dir,
+                ..DeploymentPlan::default()
+            },
+        };
+        for target in targets {
+            if !is_file(target) {
+                return Err(Error::config(format!(
+                    "Given target \"{}\" is not a file",
+                    target.display()
+                )));
+            }
+            context.visit(target, target)?;
+        }
+        for target in targets {
+            context.check_crt_conflicts(target);
+        }
+        return Ok(context.plan);
+    }
+
+    /// Copy the dlls in the plan into the output dir.
+    pub fn execute(&self, plan: &DeploymentPlan) -> Result<(), Error> {
+        for dll in &plan.dlls {
+            std::fs::copy(long_path(&dll.source), long_path(&dll.destination)).map_err(|e| {
+                let message = format!(
+                    "Failed to copy \"{}\" to \"{}\"",
+                    dll.source.display(),
+                    dll.destination.display()
+                );
+                return Error::io(message, e);
+            })?;
+        }
+        return Ok(());
+    }
+
+    /// Plan and copy dlls for `targets`. Fails if any dll is missing, unless missing dlls are
+    /// allowed.
+    pub fn deploy(&self, targets: &[PathBuf]) -> Result<DeploymentPlan, Error> {
+        let plan = self.plan(targets)?;
+        if !self.allow_missing {
+            if let Some(missing) = plan.missing.first() {
+                return Err(Error::MissingDependency {
+                    dll: missing.name.clone(),
+                    required_by: missing.required_by.clone(),
+                });
+            }
+        }
+        if !self.allow_non_redistributable {
+            if let Some(dll) = plan.non_redistributable.first() {
+                return Err(Error::NotRedistributable {
+                    dll: dll.name.clone(),
+                    reason: dll.reason.clone(),
+                    required_by: dll.required_by.clone(),
+                });
+            }
+        }
+        if self.deny_mixed_crt {
+            if let Some(conflict) = plan.crt_conflicts.first() {
+                return Err(Error::Conflict {
+                    message: conflict.to_string(),
+                });
+            }
+        }
+        self.execute(&plan)?;
+        return Ok(plan);
+    }
+}
+
+struct Context<'a> {
+    deployer: &'a Deployer,
+    state: &'a mut SearchState,
+    visited: HashSet<PathBuf>,
+    /// Deployed paths of visited binaries and the imports between them
+    graph: ModuleGraph,
+    plan: DeploymentPlan,
+}
+
+impl Context<'_> {
+    fn report(
+        &mut self,
+        kind: DiagnosticKind,
+        level: &'static str,
+        message: String,
+        importer: &Path,
+    ) {
+        let diagnostic = Diagnostic {
+            kind,
+            level,
+            message,
+            importer: importer.to_path_buf(),
+        };
+        if !self.plan.diagnostics.contains(&diagnostic) {
+            self.plan.diagnostics.push(diagnostic);
+        }
+    }
+
+    /// Try resolvers in order, returns the location and the name of the resolver that found it.
+    fn search(
+        &mut self,
+        dep: &Dependency,
+        target_binary: &Path,
+        importer: Importer,
+    ) -> Option<(PathBuf, String)> {
+        let verbose = self.deployer.verbose;
+        let dep_name = &dep.dll_name;
+        if verbose {
+            println!("Searching {dep_name} for {}", target_binary.display());
+        }
+
+        let request = ValidationRequest {
+            dll_name: dep_name,
+            importer: target_binary,
+            machine: importer.machine,
+            linker_version: importer.linker_version,
+            symbols: &dep.symbols,
+        };
+        let rejections: RefCell<Vec<RejectedCandidate>> = RefCell::new(Vec::new());
+        let validate = |path: &Path| -> Result<(), String> {
+            let (validator, reason) = match self.state.validators.check(path, &request) {
+                Ok(_) => return Ok(()),
+                Err(r) => r,
+            };
+            let message = format!("validator {validator} rejects it: {reason}");
+            let rejection = RejectedCandidate {
+                dll_name: dep_name.clone(),
+                path: path.to_path_buf(),
+                validator,
+                reason,
+                required_by: target_binary.to_path_buf(),
+            };
+            let mut rejections = rejections.borrow_mut();
+            if !rejections.contains(&rejection) {
+                rejections.push(rejection);
+            }
+            return Err(message);
+        };
+        let validate: &dyn Fn(&Path) -> Result<(), String> = &validate;
+        let validate = if self.state.validators.is_empty() {
+            None
+        } else {
+            Some(validate)
+        };
+
+        // msvcrt has no debug variant, the config of binaries that import it is the build config
+        // of the targets if given
+        let crt_config = importer.crt.map(CrtFlavor::build_config);
+        let config = match (importer.crt, self.deployer.build_config) {
+            (Some(CrtFlavor::Msvcrt) | None, Some(build_config)) => Some(build_config),
+            _ => crt_config,
+        };
+        let find = |validate: DllValidator| -> Option<(PathBuf, String, Vec<PathBuf>)> {
+            for (index, resolver) in self.state.resolvers.iter().enumerate() {
+                let location =
+                    match resolver.resolve_for(dep_name, config, importer.machine, validate) {
+                        Some(l) => l,
+                        None => continue,
+                    };
+                if verbose {
+                    println!(
+                        "Found \"{}\" by resolver {}",
+                        location.display(),
+                        resolver.name()
+                    );
+                }
+
+                // Warn if the remaining resolvers can find a different file with the same name.
+                // Candidates are searched once per name and config, and validated for every
+                // importer.
+                let mut cache = self.state.candidates.borrow_mut();
+                let all_candidates = cache
+                    .entry((dep_name.to_lowercase(), config, importer.machine))
+                    .or_insert_with(|| {
+                        return self
+                            .state
+                            .resolvers
+                            .iter()
+                            .map(|r| r.candidates_for(dep_name, config, importer.machine, None))
+                            .collect();
+                    });
+                let mut candidates = vec![location.clone()];
+                for candidate in all_candidates[index..].iter().flatten() {
+                    if candidates.contains(candidate) {
+                        continue;
+                    }
+                    let valid = match validate {
+                        Some(validate) => validate(candidate).is_ok(),
+                        None => true,
+                    };
+                    if valid {
+                        candidates.push(candidate.clone());
+                    }
+                }
+                return Some((location, resolver.name().to_string(), candidates));
+            }
+            return None;
+        };
+
+        // Prefer a dll built for the same config as the importer, a dll of the other config is
+        // only chosen if there is no such dll. Found dlls can only be checked against a config
+        // told by the C runtime of the importer.
+        let mut mismatched = None;
+        let checked_config = if config == crt_config { config } else { None };
+        let found = match checked_config {
+            Some(config) => {
+                let matches_config = |path: &Path| -> Result<(), String> {
+                    if let Some(validate) = validate {
+                        validate(path)?;
+                    }
+                    return match crt_of_file(path).map(CrtFlavor::build_config) {
+                        Some(found) if found != config => Err(format!(
+                            "it is a {found} build, but the importer is a {config} build"
+                        )),
+                        _ => Ok(()),
+                    };
+                };
+                let matches_config: &dyn Fn(&Path) -> Result<(), String> = &matches_config;
+                find(Some(matches_config)).or_else(|| {
+                    let found = find(validate)?;
+                    mismatched = crt_of_file(&found.0).map(|crt| (crt.build_config(), config));
+                    return Some(found);
+                })
+            }
+            None => find(validate),
+        };
+
+        for rejection in rejections.take() {
+            self.report(
+                DiagnosticKind::RejectedCandidate,
+                DiagnosticKind::RejectedCandidate.default_level(),
+                format!(
+                    "\"{}\" is rejected by validator {}: {}",
+                    rejection.path.display(),
+                    rejection.validator,
+                    rejection.reason
+                ),
+                target_binary,
+            );
+            if !self.plan.rejections.contains(&rejection) {
+                self.plan.rejections.push(rejection);
+            }
+        }
+
+        let (location, resolver, candidates) = found?;
+        if let Some((found, config)) = mismatched {
+            self.report(
+                DiagnosticKind::ConfigMismatch,
+                DiagnosticKind::ConfigMismatch.default_level(),
+                format!(
+                    "Only \"{}\" is found for {dep_name}, which is a {found} build, but the \
+                     importer is a {config} build",
+                    location.display()
+                ),
+                target_binary,
+            );
+        }
+        let distinct = count_distinct_files(&candidates);
+        if distinct > 1 {
+            self.report(
+                DiagnosticKind::AmbiguousCandidate,
+                DiagnosticKind::AmbiguousCandidate.default_level(),
+                format!(
+                    "{} different files named {dep_name} are found: {:?}, \"{}\" is chosen",
+                    distinct,
+                    candidates,
+                    location.display()
+                ),
+                target_binary,
+            );
+        }
+        return Some((location, resolver));
+    }
+
+    /// Report dlls reachable from `target` that use another C runtime.
+    fn check_crt_conflicts(&mut self, target: &Path) {
+        for conflict in self.graph.crt_conflicts(target) {
+            let importer = conflict
+                .boundaries
+                .first()
+                .map(|(importer, _)| importer.clone())
+                .unwrap_or_else(|| target.to_path_buf());
+            let level = if self.deployer.deny_mixed_crt {
+                "error"
+            } else {
+                DiagnosticKind::MixedCrt.default_level()
+            };
+            self.report(
+                DiagnosticKind::MixedCrt,
+                level,
+                conflict.to_string(),
+                &importer,
+            );
+            if !self.plan.crt_conflicts.contains(&conflict) {
+                self.plan.crt_conflicts.push(conflict);
+            }
+        }
+    }
+
+    /// Plan every dll of the UCRT redistributable for the machine type of the importer, so that
+    /// the forwarders and `ucrtbase.dll` come from the same SDK.
+    fn plan_ucrt(
+        &mut self,
+        dep: &str,
+        target_binary: &Path,
+        target_machine: Option<u16>,
+    ) -> Result<(), Error> {
+        let verbose = self.deployer.verbose;
+        if self.plan.dlls.iter().any(|d| d.resolver == UCRT_RESOLVER) {
+            if verbose {
+                println!("{dep} is deployed with the UCRT redistributable");
+            }
+            return Ok(());
+        }
+        let dir = match &self.deployer.ucrt_dir {
+            Some(dir) => dir,
+            None => return Ok(()),
+        };
+        let arch_dir = target_machine.and_then(|machine| ucrt_redist_dir(dir, machine));
+        let arch_dir = match arch_dir {
+            Some(arch_dir) => arch_dir,
+            None => {
+                let arch = target_machine.map(machine_name).unwrap_or_default();
+                return Err(Error::config(format!(
+                    "UCRT redistributable for {arch} is not found in \"{}\", required by \"{}\"",
+                    dir.display(),
+                    target_binary.display()
+                )));
+            }
+        };
+        let dlls = ucrt_redist_dlls(&arch_dir);
+        if verbose {
+            println!(
+                "Deploying {} UCRT dll(s) from \"{}\" for {dep}",
+                dlls.len(),
+                arch_dir.display()
+            );
+        }
+        for (name, source) in dlls {
+            if self.plan.dlls.iter().any(|d| d.name == name) {
+                continue;
+            }
+            self.plan.dlls.push(PlannedDll {
+                destination: self.plan.output_dir.join(&name),
+                name,
+                source,
+                required_by: target_binary.to_path_buf(),
+                resolver: UCRT_RESOLVER.to_string(),
+                port: None,
+            });
+        }
+        return Ok(());
+    }
+
+    /// `file` is read to find dependencies, `target_binary` is where it is or will be deployed.
+    fn visit(&mut self, file: &Path, target_binary: &Path) -> Result<(), Error> {
+        if !self.visited.insert(file.to_path_buf()) {
+            return Ok(());
+        }
+        let verbose = self.deployer.verbose;
+        let target_dir = self.plan.output_dir.clone();
+        if verbose {
+            println!(
+                "Deploying for \"{}\" at \"{}\"",
+                target_binary.display(),
+                target_dir.display()
+            );
+        }
+        let deps = get_dependencies(file, verbose)?;
+        if verbose {
+            let names: Vec<&String> = deps.iter().map(|d| &d.dll_name).collect();
+            println!("\"{}\" requires {:?}", target_binary.display(), names)
+        }
+        let target_machine = get_machine(file);
+        let crt = classify_crt(deps.iter().map(|d| d.dll_name.as_str()));
+        if verbose {
+            if let Some(crt) = crt {
+                println!("\"{}\" uses {crt}", target_binary.display());
+            }
+        }
+        self.graph.add_module(target_binary, crt);
+        let importer = Importer {
+            machine: target_machine,
+            linker_version: get_linker_version(file),
+            crt,
+        };
+
+        // Dlls of the redistributable deployed together with the imported ones, they are skipped
+        // if not found
+        let mut siblings: Vec<Dependency> = Vec::new();
+        if self.deployer.vc_redist == VcRedistPolicy::Copy {
+            for dependency in &deps {
+                for name in runtime_family(&dependency.dll_name) {
+                    let known = deps.iter().chain(&siblings).any(|d| d.dll_name == name);
+                    if !known {
+                        siblings.push(Dependency {
+                            dll_name: name,
+                            symbols: Vec::new(),
+                        });
+                    }
+                }
+            }
+        }
+
+        for (dependency, imported) in deps
+            .iter()
+            .map(|d| (d, true))
+            .chain(siblings.iter().map(|d| (d, false)))
+        {
+            let dep = &dependency.dll_name;
+            if verbose {
+                println!("Searching {dep} for \"{}\"", target_binary.display())
+            }
+
+            if self
+                .deployer
+                .ignore
+                .iter()
+                .any(|i| i.eq_ignore_ascii_case(dep))
+            {
+                // The dll is assigned to be ignored
+                if verbose {
+                    println!("Skip {dep} because it is assigned to be ignored");
+                }
+                continue;
+            }
+
+            let is_vc_redist = is_vc_redist_dll(dep);
+            let is_app_local_ucrt = self.deployer.ucrt_dir.is_some()
+                && is_ucrt_dll(dep)
+                && !self.deployer.overrides.contains_key(&dep.to_lowercase());
+
+            if is_app_local_ucrt {
+                self.plan_ucrt(dep, target_binary, target_machine)?;
+                continue;
+            }
+
+            if self.deployer.vc_redist == VcRedistPolicy::Skip && is_vc_redist {
+                // Skip vc redist dll.
+                if verbose {
+                    match classify_runtime_dll(dep).and_then(|d| Some((d.component, d.toolset()?)))
+                    {
+                        Some((component, toolset)) => println!(
+                            "Skip VC redistributable dll {dep}, {component} of toolset {toolset}"
+                        ),
+                        None => println!("Skip VC redistributable dll {dep}"),
+                    }
+                }
+                continue;
+            }
+
+            if is_system_dll(dep) && !is_vc_redist {
+                // Skip system dll
+                if verbose {
+                    println!("Skip system dll {dep}");
+                }
+                continue;
+            }
+
+            let expected_filename = target_dir.join(dep);
+            let location = if let Some(source) = self.deployer.overrides.get(&dep.to_lowercase()) {
+                if !self.plan.dlls.iter().any(|d| d.name == *dep) {
+                    if verbose {
+                        println!("Override {dep} with \"{}\"", source.display());
+                    }
+                    self.plan.dlls.push(PlannedDll {
+                        name: dep.clone(),
+                        source: source.clone(),
+                        destination: expected_filename.clone(),
+                        required_by: target_binary.to_path_buf(),
+                        resolver: "override".to_string(),
+                        port: None,
+                    });
+                }
+                source.clone()
+            } else if is_file(&expected_filename) {
+                expected_filename.clone()
+            } else if let Some(planned) = self.plan.dlls.iter().find(|d| d.name == *dep) {
+                planned.source.clone()
+            } else if let Some((location, resolver)) = self
+                .state
+                .found
+                .get(&(dep.to_lowercase(), importer))
+                .cloned()
+                .or_else(|| {
+                    return self.search(dependency, target_binary, importer);
+                })
+            {
+                self.state.found.insert(
+                    (dep.to_lowercase(), importer),
+                    (location.clone(), resolver.clone()),
+                );
+                let port = self.state.ports.get(&path_key(&location)).cloned();
+                if let (true, Some(port)) = (verbose, &port) {
+                    println!(
+                        "\"{}\" is installed by vcpkg port {port}",
+                        location.display()
+                    );
+                }
+                self.plan.dlls.push(PlannedDll {
+                    name: dep.clone(),
+                    source: location.clone(),
+                    destination: expected_filename.clone(),
+                    required_by: target_binary.to_path_buf(),
+                    resolver,
+                    port,
+                });
+                location
+            } else if !imported {
+                if verbose {
+                    println!("Skip {dep}, it is not imported and can't be found");
+                }
+                continue;
+            } else {
+                let level = if self.deployer.allow_missing {
+                    "warning"
+                } else {
+                    "error"
+                };
+                let rejected = self
+                    .plan
+                    .rejections
+                    .iter()
+                    .filter(|r| r.dll_name == *dep && r.required_by == target_binary)
+                    .count();
+                let message = if rejected == 0 {
+                    format!("Failed to find dll \"{dep}\"")
+                } else {
+                    format!("Failed to find dll \"{dep}\", {rejected} candidate(s) are rejected")
+                };
+                self.report(DiagnosticKind::MissingDll, level, message, target_binary);
+                self.plan.missing.push(MissingDll {
+                    name: dep.clone(),
+                    required_by: target_binary.to_path_buf(),
+                });
+                continue;
+            };
+
+            if let Some(reason) = non_redistributable_reason(dep) {
+                let (kind, message) = if is_debug_crt_dll(dep) {
+                    (
+                        DiagnosticKind::DebugCrtShipped,
+                        format!(
+                            "Debug C runtime {dep} is deployed to \"{}\"",
+                            target_dir.display()
+                        ),
+                    )
+                } else {
+                    (
+                        DiagnosticKind::NonRedistributableShipped,
+                        format!(
+                            "{dep} is deployed to \"{}\", but {reason}",
+                            target_dir.display()
+                        ),
+                    )
+                };
+                let level = if self.deployer.allow_non_redistributable {
+                    kind.default_level()
+                } else {
+                    "error"
+                };
+                self.report(kind, level, message, target_binary);
+                let dll = NonRedistributableDll {
+                    name: dep.to_lowercase(),
+                    reason: reason.to_string(),
+                    required_by: target_binary.to_path_buf(),
+                };
+                if !self.plan.non_redistributable.contains(&dll) {
+                    self.plan.non_redistributable.push(dll);
+                }
+            }
+
+            let dll_machine = get_machine(&location);
+            if let (Some(target_machine), Some(dll_machine)) = (target_machine, dll_machine) {
+                if target_machine != dll_machine {
+                    self.report(
+                        DiagnosticKind::ArchitectureMismatch,
+                        DiagnosticKind::ArchitectureMismatch.default_level(),
+                        format!(
+                            "\"{}\" is built for {}, but the importer is built for {}",
+                            expected_filename.display(),
+                            machine_name(dll_machine),
+                            machine_name(target_machine)
+                        ),
+                        target_binary,
+                    );
+                }
+            }
+
+            self.graph.add_edge(target_binary, &expected_filename);
+            self.visit(&location, &expected_filename)?;
+        }
+        return Ok(());
+    }
+}
+
+/// Number of files with different content. Files are only hashed if another file has the same
+/// size, files that can't be read are not counted.
+fn count_distinct_files(paths: &[PathBuf]) -> usize {
+    let mut by_size: HashMap<u64, Vec<&PathBuf>> = HashMap::new();
+    for path in paths {
+        if let Ok(md) = std::fs::metadata(long_path(path)) {
+            by_size.entry(md.len()).or_default().push(path);
+        }
+    }
+    return by_size
+        .values()
+        .map(|same_size| {
+            if same_size.len() == 1 {
+                return 1;
+            }
+            let hashes: HashSet<String> = same_size
+                .iter()
+                .filter_map(|p| std::fs::read(long_path(p)).ok())
+                .map(|bytes| sha256_hex(&bytes))
+                .collect();
+            return hashes.len();
+        })
+        .sum();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_pe::TestPe;
+    use crate::util::test_dir;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn searches_build_dir_after_runtime_output_dir() {
+        let build_dir = test_dir("deployer-build-dir");
+        let bin = build_dir.join("bin");
+        let exe = bin.join("app.exe");
+        TestPe::exe()
+            .import("foo.dll", &["foo"])
+            .import("bar.dll", &["bar"])
+            .write(&exe);
+        TestPe::dll().export("bar").write(&bin.join("bar.dll"));
+        let foo = build_dir.join("src").join("foo").join("foo.dll");
+        TestPe::dll().export("foo").write(&foo);
+        let text = format!(
+            "CMAKE_RUNTIME_OUTPUT_DIRECTORY:PATH={}\nCMAKE_BUILD_TYPE:STRING=Release\n",
+            bin.display()
+        );
+        let cache = CmakeCache::parse(&text, build_dir.clone());
+
+        let deployer = Deployer::new().output_dir(build_dir.join("out"));
+        let targets = [exe];
+        let plan = deployer.clone().cmake_cache(&cache).plan(&targets);
+        let plan = plan.unwrap();
+        let found: Vec<(&str, &Path, &str)> = plan
+            .dlls
+            .iter()
+            .map(|d| (d.name.as_str(), d.source.as_path(), d.resolver.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("foo.dll", foo.as_path(), "build-dir"),
+                ("bar.dll", bin.join("bar.dll").as_path(), "shallow"),
+            ]
+        );
+        assert!(plan.missing.is_empty());
+
+        let deployer = deployer.cmake_cache(&cache).deep_search(false);
+        let missing = deployer.plan(&targets).unwrap().missing;
+        let missing: Vec<&str> = missing.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(missing, ["foo.dll"]);
+    }
+
+    #[test]
+    fn skips_runtime_dlls_shipped_with_windows() {
+        let dir = test_dir("deployer-os-runtime");
+        let exe = dir.join("app.exe");
+        TestPe::exe()
+            .import("mfc42.dll", &[])
+            .import("msvcrt.dll", &[])
+            .import("msvcp140.dll", &[])
+            .write(&exe);
+        let search_dir = dir.join("redist");
+        for name in ["mfc42.dll", "msvcrt.dll", "msvcp140.dll", "msvcp140_1.dll"] {
+            TestPe::dll()
+                .file_version("14.40.33810.0")
+                .write(&search_dir.join(name));
+        }
+        let plan = Deployer::new()
+            .shallow_search_dir(&search_dir)
+            .output_dir(dir.join("out"))
+            .vc_redist(VcRedistPolicy::Copy)
+            .plan(&[exe])
+            .unwrap();
+        let names: Vec<&str> = plan.dlls.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["msvcp140.dll", "msvcp140_1.dll"]);
+        assert!(plan.missing.is_empty());
+    }
+
+    fn diagnostic_kinds(plan: &DeploymentPlan) -> Vec<DiagnosticKind> {
+        return plan.diagnostics.iter().map(|d| d.kind).collect();
+    }
+
+    #[test]
+    fn warns_about_different_dlls_with_the_same_name() {
+        let dir = test_dir("deployer-ambiguous");
+        let app = dir.join("app").join("app.exe");
+        let tool = dir.join("tool").join("tool.exe");
+        for exe in [&app, &tool] {
+            TestPe::exe().import("foo.dll", &[]).write(exe);
+        }
+        let first = dir.join("first").join("foo.dll");
+        let second = dir.join("second").join("foo.dll");
+        TestPe::dll().timestamp(1).write(&first);
+        TestPe::dll().timestamp(2).write(&second);
+        let deployer = Deployer::new()
+            .shallow_search_dir(dir.join("first"))
+            .shallow_search_dir(dir.join("second"));
+
+        let groups = [&app, &tool].map(|exe| TargetGroup {
+            output_dir: exe.parent().unwrap().to_path_buf(),
+            targets: vec![exe.clone()],
+        });
+        let plans = deployer.plan_groups(&groups).unwrap();
+        assert_eq!(plans[0].dlls[0].source, first);
+        assert_eq!(plans[1].dlls[0].source, first);
+        assert_eq!(
+            diagnostic_kinds(&plans[0]),
+            [DiagnosticKind::AmbiguousCandidate]
+        );
+
+        // Identical copies are not ambiguous
+        TestPe::dll().timestamp(1).write(&second);
+        let plan = deployer.plan(&[app]).unwrap();
+        assert_eq!(plan.dlls[0].source, first);
+        assert!(plan.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn prefers_dlls_of_the_same_config() {
+        let dir = test_dir("deployer-config");
+        let exe = dir.join("app.exe");
+        TestPe::exe()
+            .import("ucrtbased.dll", &[])
+            .import("foo.dll", &[])
+            .write(&exe);
+        let release = dir.join("release").join("foo.dll");
+        let debug = dir.join("debug").join("foo.dll");
+        TestPe::dll().import("ucrtbase.dll", &[]).write(&release);
+        TestPe::dll().import("ucrtbased.dll", &[]).write(&debug);
+        let targets = [exe];
+
+        let deployer = Deployer::new()
+            .output_dir(dir.join("out"))
+            .shallow_search_dir(dir.join("release"))
+            .shallow_search_dir(dir.join("debug"));
+        let plan = deployer.plan(&targets).unwrap();
+        let foo = plan.dlls.iter().find(|d| d.name == "foo.dll").unwrap();
+        assert_eq!(foo.source, debug);
+        assert!(!diagnostic_kinds(&plan).contains(&DiagnosticKind::ConfigMismatch));
+
+        // A dll of the other config is chosen only if there is no dll of the same config
+        std::fs::remove_file(&debug).unwrap();
+        let plan = deployer.plan(&targets).unwrap();
+        let foo = plan.dlls.iter().find(|d| d.name == "foo.dll").unwrap();
+        assert_eq!(foo.source, release);
+        assert!(diagnostic_kinds(&plan).contains(&DiagnosticKind::ConfigMismatch));
+    }
+
+    /// Finds dlls in a dir, counting how many times it is asked to
+    struct CountingResolver {
+        dir: PathBuf,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl Resolver for CountingResolver {
+        fn name(&self) -> &str {
+            return "counting";
+        }
+
+        fn resolve(&self, dll_name: &str, _validate: DllValidator) -> Option<PathBuf> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            return self.candidates(dll_name, None).pop();
+        }
+
+        fn candidates(&self, dll_name: &str, _validate: DllValidator) -> Vec<PathBuf> {
+            let path = self.dir.join(dll_name);
+            return if is_file(&path) {
+                vec![path]
+            } else {
+                Vec::new()
+            };
+        }
+    }
+
+    #[test]
+    fn shares_found_dlls_between_target_groups() {
+        let dir = test_dir("deployer-groups");
+        let groups = ["app", "tool"].map(|name| {
+            let exe = dir.join(name).join(format!("{name}.exe"));
+            TestPe::exe().import("foo.dll", &[]).write(&exe);
+            return TargetGroup {
+                output_dir: dir.join(name),
+                targets: vec![exe],
+            };
+        });
+        TestPe::dll().write(&dir.join("deps").join("foo.dll"));
+        let calls = Arc::new(AtomicUsize::new(0));
+        let deployer = Deployer::new()
+            .resolver(CountingResolver {
+                dir: dir.join("deps"),
+                calls: calls.clone(),
+            })
+            .resolver_order(&[ResolverKind::Custom]);
+
+        let plans = deployer.plan_groups(&groups).unwrap();
+        let planned: Vec<(&Path, &str)> = plans
+            .iter()
+            .flat_map(|plan| &plan.dlls)
+            .map(|d| (d.destination.as_path(), d.resolver.as_str()))
+            .collect();
+        let app_foo = dir.join("app").join("foo.dll");
+        let tool_foo = dir.join("tool").join("foo.dll");
+        assert_eq!(
+            planned,
+            [
+                (app_foo.as_path(), "counting"),
+                (tool_foo.as_path(), "counting")
+            ]
+        );
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+        // Groups planned one by one search again
+        for group in &groups {
+            deployer.plan(&group.targets).unwrap();
+        }
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn refuses_non_redistributable_dlls() {
+        let dir = test_dir("deployer-non-redistributable");
+        let exe = dir.join("app.exe");
+        TestPe::exe().import("vcruntime140d.dll", &[]).write(&exe);
+        let deps = dir.join("deps");
+        TestPe::dll()
+            .file_version("14.40.33810.0")
+            .write(&deps.join("vcruntime140d.dll"));
+        let out = dir.join("out");
+        let deployer = Deployer::new()
+            .shallow_search_dir(&deps)
+            .vc_redist(VcRedistPolicy::Copy)
+            .output_dir(&out);
+        let targets = [exe];
+
+        let plan = deployer.plan(&targets).unwrap();
+        let refused: Vec<&str> = plan
+            .non_redistributable
+            .iter()
+            .map(|d| d.name.as_str())
+            .collect();
+        assert_eq!(refused, ["vcruntime140d.dll"]);
+        let diagnostics: Vec<(DiagnosticKind, &str)> =
+            plan.diagnostics.iter().map(|d| (d.kind, d.level)).collect();
+        assert_eq!(diagnostics, [(DiagnosticKind::DebugCrtShipped, "error")]);
+        let error = deployer.deploy(&targets).unwrap_err();
+        assert!(matches!(
+            &error,
+            Error::NotRedistributable { dll, .. } if dll == "vcruntime140d.dll"
+        ));
+        assert_eq!(error.exit_code(), 8);
+        assert!(!out.join("vcruntime140d.dll").exists());
+
+        // Allowed for dirs that are not shipped, like build dirs
+        std::fs::create_dir_all(&out).unwrap();
+        let plan = deployer
+            .allow_non_redistributable(true)
+            .deploy(&targets)
+            .unwrap();
+        assert!(plan.diagnostics.iter().all(|d| d.level == "warning"));
+        assert!(out.join("vcruntime140d.dll").exists());
+    }
+
+    #[test]
+    fn deploys_vcpkg_dlls_of_the_config() {
+        let dir = test_dir("deployer-vcpkg-config");
+        let triplet = dir.join("installed/x64-windows");
+        std::fs::create_dir_all(dir.join("installed/vcpkg")).unwrap();
+        std::fs::write(dir.join("installed/vcpkg/status"), "").unwrap();
+        let release = triplet.join("bin/fmt.dll");
+        let debug = triplet.join("debug/bin/fmt.dll");
+        TestPe::dll().import("ucrtbase.dll", &[]).write(&release);
+        TestPe::dll().import("ucrtbased.dll", &[]).write(&debug);
+        let debug_exe = dir.join("debug/app.exe");
+        let release_exe = dir.join("release/app.exe");
+        TestPe::exe()
+            .import("ucrtbased.dll", &[])
+            .import("fmt.dll", &[])
+            .write(&debug_exe);
+        TestPe::exe()
+            .import("ucrtbase.dll", &[])
+            .import("fmt.dll", &[])
+            .write(&release_exe);
+        let deployer = Deployer::new()
+            .search_env_cmake_prefix_path(false)
+            .cmake_prefix_path(&triplet);
+
+        for (exe, expected) in [(debug_exe, debug), (release_exe, release)] {
+            let plan = deployer.plan(&[exe]).unwrap();
+            let fmt = plan.dlls.iter().find(|d| d.name == "fmt.dll").unwrap();
+            assert_eq!(fmt.source, expected);
+            assert_eq!(fmt.resolver, "vcpkg");
+            assert!(!diagnostic_kinds(&plan).contains(&DiagnosticKind::ConfigMismatch));
+        }
+    }
+}
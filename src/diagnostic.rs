@@ -0,0 +1,245 @@
+use std::path::{Path, PathBuf};
+
+/// Problems found during deployment. Rule ids are stable, don't reuse or renumber them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    MissingDll,
+    ArchitectureMismatch,
+    AmbiguousCandidate,
+    DebugCrtShipped,
+    RejectedCandidate,
+    NonRedistributableShipped,
+    MixedCrt,
+    ConfigMismatch,
+}
+
+impl DiagnosticKind {
+    pub const ALL: [DiagnosticKind; 8] = [
+        DiagnosticKind::MissingDll,
+        DiagnosticKind::ArchitectureMismatch,
+        DiagnosticKind::AmbiguousCandidate,
+        DiagnosticKind::DebugCrtShipped,
+        DiagnosticKind::RejectedCandidate,
+        DiagnosticKind::NonRedistributableShipped,
+        DiagnosticKind::MixedCrt,
+        DiagnosticKind::ConfigMismatch,
+    ];
+
+    pub fn rule_id(self) -> &'static str {
+        return match self {
+            DiagnosticKind::MissingDll => "DLLD001",
+            DiagnosticKind::ArchitectureMismatch => "DLLD002",
+            DiagnosticKind::AmbiguousCandidate => "DLLD003",
+            DiagnosticKind::DebugCrtShipped => "DLLD004",
+            DiagnosticKind::RejectedCandidate => "DLLD005",
+            DiagnosticKind::NonRedistributableShipped => "DLLD006",
+            DiagnosticKind::MixedCrt => "DLLD007",
+            DiagnosticKind::ConfigMismatch => "DLLD008",
+        };
+    }
+
+    pub fn name(self) -> &'static str {
+        return match self {
+            DiagnosticKind::MissingDll => "MissingDll",
+            DiagnosticKind::ArchitectureMismatch => "ArchitectureMismatch",
+            DiagnosticKind::AmbiguousCandidate => "AmbiguousCandidate",
+            DiagnosticKind::DebugCrtShipped => "DebugCrtShipped",
+            DiagnosticKind::RejectedCandidate => "RejectedCandidate",
+            DiagnosticKind::NonRedistributableShipped => "NonRedistributableShipped",
+            DiagnosticKind::MixedCrt => "MixedCrt",
+            DiagnosticKind::ConfigMismatch => "ConfigMismatch",
+        };
+    }
+
+    pub fn description(self) -> &'static str {
+        return match self {
+            DiagnosticKind::MissingDll => "A dll required by the binary can't be found",
+            DiagnosticKind::ArchitectureMismatch => {
+                "A dll deployed for the binary is built for another architecture"
+            }
+            DiagnosticKind::AmbiguousCandidate => {
+                "Different dlls with the same name are found in search dirs"
+            }
+            DiagnosticKind::DebugCrtShipped => {
+                "A debug C runtime dll is deployed, which is not redistributable"
+            }
+            DiagnosticKind::RejectedCandidate => "A candidate dll is rejected by a validator",
+            DiagnosticKind::NonRedistributableShipped => {
+                "A dll that is not redistributable is deployed, other than the debug C runtime"
+            }
+            DiagnosticKind::MixedCrt => {
+                "A dll uses another C runtime than the binary it is deployed for"
+            }
+            DiagnosticKind::ConfigMismatch => {
+                "Only a dll of the other build config is found, like a release dll for a debug build"
+            }
+        };
+    }
+
+    pub fn default_level(self) -> &'static str {
+        return match self {
+            DiagnosticKind::MissingDll => "error",
+            _ => "warning",
+        };
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub kind: DiagnosticKind,
+    /// SARIF level, `error` or `warning`
+    pub level: &'static str,
+    pub message: String,
+    /// The binary that imports the problematic dll
+    pub importer: PathBuf,
+}
+
+/// Percent-encode a path into a `file://` uri.
+fn path_to_file_uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut uri = String::from("file://");
+    if !path.starts_with('/') {
+        uri.push('/');
+    }
+    for b in path.bytes() {
+        if b.is_ascii_alphanumeric() || b"-._~/:".contains(&b) {
+            uri.push(b as char);
+        } else {
+            uri.push_str(&format!("%{b:02X}"));
+        }
+    }
+    return uri;
+}
+
+/// Build a SARIF 2.1.0 log from the diagnostics.
+pub fn sarif_log(diagnostics: &[Diagnostic]) -> serde_json::Value {
+    use serde_json::json;
+    let rules: Vec<serde_json::Value> = DiagnosticKind::ALL
+        .iter()
+        .map(|kind| {
+            json!({
+                "id": kind.rule_id(),
+                "name": kind.name(),
+                "shortDescription": { "text": kind.description() },
+                "defaultConfiguration": { "level": kind.default_level() },
+            })
+        })
+        .collect();
+    let results: Vec<serde_json::Value> = diagnostics
+        .iter()
+        .map(|d| {
+            json!({
+                "ruleId": d.kind.rule_id(),
+                "ruleIndex": DiagnosticKind::ALL.iter().position(|k| *k == d.kind).unwrap(),
+                "level": d.level,
+                "message": { "text": d.message },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": path_to_file_uri(&d.importer) }
+                    }
+                }],
+            })
+        })
+        .collect();
+    return json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "deploy-dll",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }],
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn keeps_rule_ids_stable() {
+        let ids: Vec<&str> = DiagnosticKind::ALL.iter().map(|k| k.rule_id()).collect();
+        assert_eq!(
+            ids,
+            [
+                "DLLD001", "DLLD002", "DLLD003", "DLLD004", "DLLD005", "DLLD006", "DLLD007",
+                "DLLD008"
+            ]
+        );
+    }
+
+    #[test]
+    fn writes_sarif_log() {
+        let diagnostics = [
+            Diagnostic {
+                kind: DiagnosticKind::MissingDll,
+                level: "error",
+                message: "Failed to find dll \"foo.dll\"".to_string(),
+                importer: PathBuf::from(r"C:\Program Files\app #1\app.exe"),
+            },
+            Diagnostic {
+                kind: DiagnosticKind::ConfigMismatch,
+                level: "warning",
+                message: "Only a release build of bar.dll is found".to_string(),
+                importer: PathBuf::from("/opt/app/bin/app.exe"),
+            },
+        ];
+        let log = sarif_log(&diagnostics);
+        assert_eq!(log["version"], "2.1.0");
+
+        let rules = &log["runs"][0]["tool"]["driver"]["rules"];
+        for (index, kind) in DiagnosticKind::ALL.iter().enumerate() {
+            assert_eq!(rules[index]["id"], kind.rule_id());
+            assert_eq!(rules[index]["name"], kind.name());
+        }
+        assert_eq!(
+            rules[7],
+            json!({
+                "id": "DLLD008",
+                "name": "ConfigMismatch",
+                "shortDescription": {
+                    "text": "Only a dll of the other build config is found, like a release dll \
+                             for a debug build"
+                },
+                "defaultConfiguration": { "level": "warning" },
+            })
+        );
+
+        assert_eq!(
+            log["runs"][0]["results"],
+            json!([
+                {
+                    "ruleId": "DLLD001",
+                    "ruleIndex": 0,
+                    "level": "error",
+                    "message": { "text": "Failed to find dll \"foo.dll\"" },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": {
+                                "uri": "file:///C:/Program%20Files/app%20%231/app.exe"
+                            }
+                        }
+                    }],
+                },
+                {
+                    "ruleId": "DLLD008",
+                    "ruleIndex": 7,
+                    "level": "warning",
+                    "message": { "text": "Only a release build of bar.dll is found" },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": { "uri": "file:///opt/app/bin/app.exe" }
+                        }
+                    }],
+                },
+            ])
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use crate::util::format_size;
+use std::path::PathBuf;
+
+/// Errors of the library and the `deploy-dll` executable.
+///
+/// Every kind of error maps to an exit code of `deploy-dll`, codes are stable:
+///
+/// | Code | Error |
+/// |------|-------|
+/// | 1 | [`Error::MissingDependency`] |
+/// | 2 | Invalid command line arguments, reported by clap |
+/// | 3 | [`Error::Parse`] |
+/// | 4 | [`Error::Io`] |
+/// | 5 | [`Error::Config`] |
+/// | 6 | [`Error::SizeBudgetExceeded`] |
+/// | 7 | [`Error::Conflict`] |
+/// | 8 | [`Error::NotRedistributable`] |
+#[derive(Debug)]
+pub enum Error {
+    /// A file can't be parsed, for example a corrupt PE file or manifest
+    Parse { path: PathBuf, message: String },
+    /// Failed to read, write or copy a file
+    Io {
+        message: String,
+        source: std::io::Error,
+    },
+    /// A dll required by a binary can't be found
+    MissingDependency { dll: String, required_by: PathBuf },
+    /// Dlls that can't be deployed together
+    Conflict { message: String },
+    /// A dll that must not be shipped is deployed, like the debug C runtime
+    NotRedistributable {
+        dll: String,
+        reason: String,
+        required_by: PathBuf,
+    },
+    /// Invalid options or targets
+    Config { message: String },
+    /// The total size of targets and deployed dlls exceeds the size budget
+    SizeBudgetExceeded { total: u64, budget: u64 },
+}
+
+impl Error {
+    pub fn io(message: impl Into<String>, source: std::io::Error) -> Error {
+        return Error::Io {
+            message: message.into(),
+            source,
+        };
+    }
+
+    pub fn config(message: impl Into<String>) -> Error {
+        return Error::Config {
+            message: message.into(),
+        };
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        return match self {
+            Error::MissingDependency { .. } => 1,
+            Error::Parse { .. } => 3,
+            Error::Io { .. } => 4,
+            Error::Config { .. } => 5,
+            Error::SizeBudgetExceeded { .. } => 6,
+            Error::Conflict { .. } => 7,
+            Error::NotRedistributable { .. } => 8,
+        };
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            Error::Parse { path, message } => {
+                write!(f, "Failed to parse \"{}\": {message}", path.display())
+            }
+            Error::Io { message, source } => write!(f, "{message} because {source}"),
+            Error::MissingDependency { dll, required_by } => write!(
+                f,
+                "Failed to find dll \"{dll}\", required by \"{}\"",
+                required_by.display()
+            ),
+            Error::Conflict { message } => write!(f, "{message}"),
+            Error::NotRedistributable {
+                dll,
+                reason,
+                required_by,
+            } => write!(
+                f,
+                "Refuse to deploy dll \"{dll}\", required by \"{}\": {reason}",
+                required_by.display()
+            ),
+            Error::Config { message } => write!(f, "{message}"),
+            Error::SizeBudgetExceeded { total, budget } => write!(
+                f,
+                "Total deployed size {} exceeds the size budget {}",
+                format_size(*total),
+                format_size(*budget)
+            ),
+        };
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        return match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        };
+    }
+}
@@ -0,0 +1,35 @@
+//! Deploy dlls for your exectuable, useful for redistributing your application as binaries.
+//!
+//! [`Deployer`] resolves the dlls required by exes or dlls and copies them next to the targets.
+//! The `deploy-dll` executable is a command line interface over this library.
+#![allow(clippy::needless_return)]
+
+pub mod cmake;
+pub mod config;
+pub mod crt;
+pub mod deployer;
+pub mod diagnostic;
+pub mod error;
+pub mod manifest;
+pub mod msvc;
+pub mod pe;
+pub mod report;
+pub mod resolver;
+pub mod scan;
+mod search;
+pub mod system;
+#[cfg(test)]
+mod test_pe;
+pub mod toolchain;
+pub mod util;
+pub mod validator;
+pub mod vcpkg;
+
+pub use deployer::{
+    Deployer, DeploymentPlan, MissingDll, NonRedistributableDll, PlannedDll, TargetGroup,
+    VcRedistPolicy,
+};
+pub use diagnostic::{sarif_log, Diagnostic, DiagnosticKind};
+pub use error::Error;
+pub use resolver::{Resolver, ResolverKind};
+pub use validator::{Validator, ValidatorKind};
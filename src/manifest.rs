@@ -0,0 +1,261 @@
+use crate::error::Error;
+use crate::pe::machine_name;
+use crate::util::{can_be_dir, is_file, long_path, walk_dir};
+use pelite::PeFile;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// A dll shipped in a deployed dir.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DllRecord {
+    /// Path relative to the deployed dir, separated by `/`
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+    /// Machine type in PE file header, `None` if the file is not a valid PE
+    pub machine: Option<String>,
+    pub file_version: Option<String>,
+    pub product_version: Option<String>,
+}
+
+/// All dlls shipped in a deployed dir, written by `--manifest` and read by `diff`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Manifest {
+    pub generator: String,
+    pub dlls: Vec<DllRecord>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Change<T> {
+    pub old: T,
+    pub new: T,
+}
+
+impl<T: PartialEq> Change<T> {
+    fn of(old: T, new: T) -> Option<Change<T>> {
+        if old == new {
+            return None;
+        }
+        return Some(Change { old, new });
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct DllChange {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_version: Option<Change<Option<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub product_version: Option<Change<Option<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<Change<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub machine: Option<Change<Option<String>>>,
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct DiffReport {
+    pub added: Vec<DllRecord>,
+    pub removed: Vec<DllRecord>,
+    pub changed: Vec<DllChange>,
+}
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    return Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect();
+}
+
+fn dll_record(file: &Path, relative_path: String) -> Result<DllRecord, Error> {
+    let bytes = std::fs::read(long_path(file))
+        .map_err(|e| Error::io(format!("Failed to read \"{}\"", file.display()), e))?;
+    let sha256 = sha256_hex(&bytes);
+
+    let mut record = DllRecord {
+        path: relative_path,
+        size: bytes.len() as u64,
+        sha256,
+        machine: None,
+        file_version: None,
+        product_version: None,
+    };
+    if let Ok(image) = PeFile::from_bytes(&bytes) {
+        record.machine = Some(machine_name(image.file_header().Machine));
+        let fixed = image
+            .resources()
+            .ok()
+            .and_then(|res| res.version_info().ok())
+            .and_then(|info| info.fixed());
+        if let Some(fixed) = fixed {
+            record.file_version = Some(fixed.dwFileVersion.to_string());
+            record.product_version = Some(fixed.dwProductVersion.to_string());
+        }
+    }
+    return Ok(record);
+}
+
+/// Collect every dll under `dir` recursively.
+pub fn scan_deployed_dir(dir: &Path) -> Result<Manifest, Error> {
+    use walkdir::WalkDir;
+    let mut dlls = Vec::new();
+    for entry in walk_dir(dir, WalkDir::sort_by_file_name) {
+        let (path, _) = entry.map_err(|e| {
+            return Error::io(format!("Failed to scan \"{}\"", dir.display()), e.into());
+        })?;
+        let path = path.as_path();
+        let is_dll = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case("dll"));
+        if !is_dll || !is_file(&path) {
+            continue;
+        }
+        let relative_path = path
+            .strip_prefix(dir)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        dlls.push(dll_record(path, relative_path)?);
+    }
+    return Ok(Manifest {
+        generator: format!("deploy-dll {}", env!("CARGO_PKG_VERSION")),
+        dlls,
+    });
+}
+
+/// Load a manifest from a json file, or scan a deployed dir.
+pub fn load_manifest(path: &Path) -> Result<Manifest, Error> {
+    if can_be_dir(&path) {
+        return scan_deployed_dir(path);
+    }
+    let content = std::fs::read_to_string(long_path(path))
+        .map_err(|e| Error::io(format!("Failed to read \"{}\"", path.display()), e))?;
+    return serde_json::from_str(&content).map_err(|e| Error::Parse {
+        path: path.to_path_buf(),
+        message: format!("not a valid manifest, {e}"),
+    });
+}
+
+pub fn diff_manifests(old: Manifest, new: Manifest) -> DiffReport {
+    // Dll names are case-insensitive on Windows
+    let index = |m: Manifest| -> BTreeMap<String, DllRecord> {
+        return m
+            .dlls
+            .into_iter()
+            .map(|r| (r.path.to_lowercase(), r))
+            .collect();
+    };
+    let mut old = index(old);
+    let new = index(new);
+
+    let mut report = DiffReport::default();
+    for (key, new_record) in new {
+        let old_record = match old.remove(&key) {
+            Some(r) => r,
+            None => {
+                report.added.push(new_record);
+                continue;
+            }
+        };
+        let change = DllChange {
+            path: new_record.path,
+            file_version: Change::of(old_record.file_version, new_record.file_version),
+            product_version: Change::of(old_record.product_version, new_record.product_version),
+            sha256: Change::of(old_record.sha256, new_record.sha256),
+            machine: Change::of(old_record.machine, new_record.machine),
+        };
+        if change.file_version.is_some()
+            || change.product_version.is_some()
+            || change.sha256.is_some()
+            || change.machine.is_some()
+        {
+            report.changed.push(change);
+        }
+    }
+    report.removed = old.into_values().collect();
+    return report;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(path: &str, sha256: &str, machine: &str, file_version: Option<&str>) -> DllRecord {
+        return DllRecord {
+            path: path.to_string(),
+            size: 1024,
+            sha256: sha256.to_string(),
+            machine: Some(machine.to_string()),
+            file_version: file_version.map(str::to_string),
+            product_version: None,
+        };
+    }
+
+    fn manifest(dlls: Vec<DllRecord>) -> Manifest {
+        return Manifest {
+            generator: "test".to_string(),
+            dlls,
+        };
+    }
+
+    #[test]
+    fn reports_added_and_removed_dlls() {
+        let old = manifest(vec![
+            record("zlib1.dll", "aa", "x64", None),
+            record("old.dll", "bb", "x64", None),
+        ]);
+        let new = manifest(vec![
+            record("zlib1.dll", "aa", "x64", None),
+            record("plugins/new.dll", "cc", "x64", None),
+        ]);
+        let report = diff_manifests(old, new);
+        let added: Vec<&str> = report.added.iter().map(|r| r.path.as_str()).collect();
+        let removed: Vec<&str> = report.removed.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(added, ["plugins/new.dll"]);
+        assert_eq!(removed, ["old.dll"]);
+        assert!(report.changed.is_empty());
+    }
+
+    #[test]
+    fn matches_paths_ignoring_case() {
+        let old = manifest(vec![record("Qt6Core.dll", "aa", "x64", None)]);
+        let new = manifest(vec![record("qt6core.DLL", "aa", "x64", None)]);
+        let report = diff_manifests(old, new);
+        assert!(report.added.is_empty());
+        assert!(report.removed.is_empty());
+        assert!(report.changed.is_empty());
+    }
+
+    #[test]
+    fn reports_only_changed_fields() {
+        let old = manifest(vec![
+            record("a.dll", "aa", "x64", Some("1.0.0.0")),
+            record("b.dll", "bb", "x86", None),
+        ]);
+        let new = manifest(vec![
+            record("a.dll", "a2", "x64", Some("1.1.0.0")),
+            record("b.dll", "bb", "x64", None),
+        ]);
+        let report = diff_manifests(old, new);
+        assert_eq!(report.changed.len(), 2);
+
+        let a = &report.changed[0];
+        assert_eq!(a.path, "a.dll");
+        let version = a.file_version.as_ref().unwrap();
+        assert_eq!(version.old.as_deref(), Some("1.0.0.0"));
+        assert_eq!(version.new.as_deref(), Some("1.1.0.0"));
+        assert_eq!(a.sha256.as_ref().unwrap().new, "a2");
+        assert!(a.machine.is_none());
+        assert!(a.product_version.is_none());
+
+        let b = &report.changed[1];
+        assert_eq!(b.path, "b.dll");
+        let machine = b.machine.as_ref().unwrap();
+        assert_eq!(machine.old.as_deref(), Some("x86"));
+        assert_eq!(machine.new.as_deref(), Some("x64"));
+        assert!(b.sha256.is_none());
+    }
+}
@@ -0,0 +1,529 @@
+use crate::pe::machine_name;
+use crate::util::{can_be_dir, is_file, long_path, subdirs};
+use crate::validator::FileVersion;
+use std::path::{Path, PathBuf};
+
+/// Components of the Microsoft C/C++ runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RuntimeComponent {
+    /// `vcruntime`, the runtime support of the compiler
+    VcRuntime,
+    /// `msvcp`, the C++ standard library
+    StdCpp,
+    /// `msvcr`, the C runtime of Visual Studio 2013 and older
+    LegacyCrt,
+    /// `msvcrt.dll` shipped with Windows
+    SystemCrt,
+    /// `concrt`, the Concurrency Runtime
+    ConcRt,
+    /// `vcomp`, OpenMP
+    OpenMp,
+    /// `vcamp`, C++ AMP
+    Amp,
+    /// `vccorlib`, the runtime of C++/CX
+    VcCorLib,
+    /// `mfc` and `mfcm`
+    Mfc,
+    /// Localized resources of MFC, like `mfc140chs.dll`
+    MfcLocale,
+    /// `ucrtbase`, the Universal C runtime
+    Ucrt,
+    /// `api-ms-win-crt-*`, forwarders to the UCRT
+    UcrtForwarder,
+    /// Other `api-ms-win-*` API sets, some of them are shipped with the UCRT redistributable
+    ApiSet,
+}
+
+impl RuntimeComponent {
+    pub fn name(self) -> &'static str {
+        return match self {
+            RuntimeComponent::VcRuntime => "VC runtime",
+            RuntimeComponent::StdCpp => "C++ standard library",
+            RuntimeComponent::LegacyCrt => "legacy C runtime",
+            RuntimeComponent::SystemCrt => "system C runtime",
+            RuntimeComponent::ConcRt => "Concurrency Runtime",
+            RuntimeComponent::OpenMp => "OpenMP",
+            RuntimeComponent::Amp => "C++ AMP",
+            RuntimeComponent::VcCorLib => "C++/CX runtime",
+            RuntimeComponent::Mfc => "MFC",
+            RuntimeComponent::MfcLocale => "MFC resources",
+            RuntimeComponent::Ucrt => "Universal C runtime",
+            RuntimeComponent::UcrtForwarder => "Universal C runtime forwarder",
+            RuntimeComponent::ApiSet => "API set",
+        };
+    }
+}
+
+impl std::fmt::Display for RuntimeComponent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "{}", self.name());
+    }
+}
+
+/// A dll of the Microsoft C/C++ runtime, classified by its name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeDll {
+    pub component: RuntimeComponent,
+    /// Version in the name, like `140` for `msvcp140.dll`. Dlls of version 140 are shared by
+    /// toolsets v140 to v143 (Visual Studio 2015 to 2022), older versions belong to one toolset,
+    /// like `120` to v120 (Visual Studio 2013)
+    pub version: Option<u32>,
+    /// Debug build, which can't be redistributed
+    pub debug: bool,
+}
+
+impl RuntimeDll {
+    /// Name of the platform toolset that ships the dll, like `v120`
+    pub fn toolset(&self) -> Option<String> {
+        return match self.version? {
+            140 => Some("v140-v143".to_string()),
+            version => Some(format!("v{version}")),
+        };
+    }
+
+    /// Whether Windows ships the dll, like `msvcrt.dll`, `mfc42.dll` and `msvcp60.dll`. The
+    /// redistributable only has dlls of version 70 (Visual Studio .NET 2002) and newer.
+    pub fn is_shipped_with_windows(&self) -> bool {
+        return match (self.component, self.version) {
+            (RuntimeComponent::SystemCrt, _) => true,
+            (_, Some(version)) => version < 70 && !self.debug,
+            (_, None) => false,
+        };
+    }
+}
+
+/// Name stems followed by a version number, longer stems first.
+const VERSIONED_STEMS: [(&str, RuntimeComponent); 9] = [
+    ("vcruntime", RuntimeComponent::VcRuntime),
+    ("vccorlib", RuntimeComponent::VcCorLib),
+    ("vcomp", RuntimeComponent::OpenMp),
+    ("vcamp", RuntimeComponent::Amp),
+    ("concrt", RuntimeComponent::ConcRt),
+    ("msvcp", RuntimeComponent::StdCpp),
+    ("msvcr", RuntimeComponent::LegacyCrt),
+    ("mfcm", RuntimeComponent::Mfc),
+    ("mfc", RuntimeComponent::Mfc),
+];
+
+/// Suffixes after the version of release dlls
+const RELEASE_SUFFIXES: [&str; 7] = [
+    "",
+    "_1",
+    "_2",
+    "_atomic_wait",
+    "_codecvt_ids",
+    "_threads",
+    "u",
+];
+
+/// Suffixes after the version of debug dlls
+const DEBUG_SUFFIXES: [&str; 7] = [
+    "d",
+    "_1d",
+    "_2d",
+    "d_atomic_wait",
+    "d_codecvt_ids",
+    "_threadsd",
+    "ud",
+];
+
+/// Localized resource dlls of MFC, like `mfc140chs.dll`
+const MFC_LOCALES: [&str; 10] = [
+    "chs", "cht", "deu", "enu", "esn", "fra", "ita", "jpn", "kor", "rus",
+];
+
+/// Dlls of the redistributable that are deployed together, by name stem and suffixes. A set is
+/// only complete for version 140, older versions have fewer dlls.
+const FAMILIES: [(&str, &[&str]); 4] = [
+    ("msvcp", &["", "_1", "_2", "_atomic_wait", "_codecvt_ids"]),
+    ("vcruntime", &["", "_1", "_threads"]),
+    ("mfc", &["", "u"]),
+    ("mfcm", &["", "u"]),
+];
+
+/// Classify a dll of the Microsoft C/C++ runtime by its name, `None` if it is not one.
+pub fn classify_runtime_dll(name: &str) -> Option<RuntimeDll> {
+    let name = name.to_lowercase();
+    let stem = name.strip_suffix(".dll")?;
+    let unversioned = |component, debug| {
+        return Some(RuntimeDll {
+            component,
+            version: None,
+            debug,
+        });
+    };
+    match stem {
+        "ucrtbase" => return unversioned(RuntimeComponent::Ucrt, false),
+        "ucrtbased" => return unversioned(RuntimeComponent::Ucrt, true),
+        "msvcrtd" => {
+            return Some(RuntimeDll {
+                component: RuntimeComponent::LegacyCrt,
+                version: Some(60),
+                debug: true,
+            })
+        }
+        _ => {}
+    }
+    if stem.starts_with("api-ms-win-crt-") {
+        return unversioned(RuntimeComponent::UcrtForwarder, false);
+    }
+    if stem.starts_with("api-ms-win-") {
+        return unversioned(RuntimeComponent::ApiSet, false);
+    }
+    if let Some(rest) = stem.strip_prefix("msvcrt") {
+        if rest.bytes().all(|b| b.is_ascii_digit()) {
+            return unversioned(RuntimeComponent::SystemCrt, false);
+        }
+    }
+
+    for (prefix, component) in VERSIONED_STEMS {
+        let rest = match stem.strip_prefix(prefix) {
+            Some(rest) => rest,
+            None => continue,
+        };
+        let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+        let version: u32 = match rest[..digits].parse() {
+            Ok(version) => version,
+            Err(_) => continue,
+        };
+        let suffix = &rest[digits..];
+        let (component, debug) = if RELEASE_SUFFIXES.contains(&suffix) {
+            (component, false)
+        } else if DEBUG_SUFFIXES.contains(&suffix) {
+            (component, true)
+        } else if prefix == "mfc" && MFC_LOCALES.contains(&suffix) {
+            (RuntimeComponent::MfcLocale, false)
+        } else {
+            continue;
+        };
+        return Some(RuntimeDll {
+            component,
+            version: Some(version),
+            debug,
+        });
+    }
+    return None;
+}
+
+/// Other dlls of the redistributable that should be deployed with `name`, for example
+/// `msvcp140_1.dll` and `msvcp140_atomic_wait.dll` with `msvcp140.dll`. Empty if `name` doesn't
+/// belong to such a set.
+pub fn runtime_family(name: &str) -> Vec<String> {
+    let name = name.to_lowercase();
+    let dll = match classify_runtime_dll(&name) {
+        Some(dll) => dll,
+        None => return Vec::new(),
+    };
+    if dll.debug || dll.version != Some(140) {
+        return Vec::new();
+    }
+    for (prefix, suffixes) in FAMILIES {
+        let base = format!("{prefix}140");
+        let suffix = match name
+            .strip_suffix(".dll")
+            .and_then(|s| s.strip_prefix(&base))
+        {
+            Some(suffix) => suffix,
+            None => continue,
+        };
+        if !suffixes.contains(&suffix) {
+            continue;
+        }
+        return suffixes
+            .iter()
+            .map(|s| format!("{base}{s}.dll"))
+            .filter(|n| *n != name)
+            .collect();
+    }
+    return Vec::new();
+}
+
+/// Machines that have architecture dirs under `VC/Redist/MSVC/<version>`, named by
+/// [`machine_name`]
+pub const REDIST_MACHINES: [u16; 4] = [0x8664, 0x014c, 0xaa64, 0x01c4];
+
+/// Dirs of redistributable dlls for `machine` in the layout of Visual Studio, newest toolset
+/// first. `dir` can be `VC/Redist/MSVC`, a version dir like `VC/Redist/MSVC/14.38.33130` (which
+/// is what `VCToolsRedistDir` points to), an architecture dir in it, or a `Microsoft.VC*.CRT`
+/// dir, which is used as is.
+pub fn vc_redist_dirs(dir: &Path, machine: u16) -> Vec<PathBuf> {
+    if is_redist_component_dir(dir) {
+        return vec![dir.to_path_buf()];
+    }
+    let arch = machine_name(machine);
+    let is_arch_dir = |name: &str| -> bool {
+        return REDIST_MACHINES
+            .iter()
+            .any(|m| machine_name(*m).eq_ignore_ascii_case(name));
+    };
+    let dir_name = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if is_arch_dir(&dir_name) {
+        if !dir_name.eq_ignore_ascii_case(&arch) {
+            return Vec::new();
+        }
+        return redist_component_dirs(dir);
+    }
+    let arch_dir = dir.join(&arch);
+    if can_be_dir(&arch_dir) {
+        return redist_component_dirs(&arch_dir);
+    }
+    let has_arch_dirs = REDIST_MACHINES
+        .iter()
+        .any(|m| can_be_dir(&dir.join(machine_name(*m))));
+    if has_arch_dirs {
+        return Vec::new();
+    }
+    // A dir of components copied out of the layout, its architecture is unknown
+    let components = redist_component_dirs(dir);
+    if !components.is_empty() {
+        return components;
+    }
+
+    let mut versions: Vec<(FileVersion, PathBuf)> = subdirs(dir)
+        .into_iter()
+        .filter_map(|d| {
+            let version = d.file_name()?.to_str()?.parse().ok()?;
+            return Some((version, d));
+        })
+        .collect();
+    versions.sort_by_key(|(version, _)| std::cmp::Reverse(*version));
+    return versions
+        .iter()
+        .flat_map(|(_, version_dir)| vc_redist_dirs(version_dir, machine))
+        .collect();
+}
+
+/// Whether the dir is like `Microsoft.VC143.CRT` or `Microsoft.VC143.OpenMP`
+fn is_redist_component_dir(dir: &Path) -> bool {
+    let name = match dir.file_name() {
+        Some(name) => name.to_string_lossy().to_lowercase(),
+        None => return false,
+    };
+    return name.starts_with("microsoft.vc") && !name.ends_with(".debugcrt");
+}
+
+fn redist_component_dirs(dir: &Path) -> Vec<PathBuf> {
+    return subdirs(dir)
+        .into_iter()
+        .filter(|d| is_redist_component_dir(d))
+        .collect();
+}
+
+/// Dir of the app-local UCRT dlls for `machine` in the layout of the Windows SDK, like
+/// `Windows Kits/10/Redist/ucrt/DLLs/x64`. `dir` can be the Windows Kits dir, its `Redist` dir,
+/// `ucrt/DLLs` or the architecture dir itself. Newer SDKs have versioned dirs like
+/// `Redist/10.0.22621.0/ucrt`, the newest one is used.
+pub fn ucrt_redist_dir(dir: &Path, machine: u16) -> Option<PathBuf> {
+    let has_ucrtbase = |d: &PathBuf| is_file(&d.join("ucrtbase.dll"));
+    if has_ucrtbase(&dir.to_path_buf()) {
+        return Some(dir.to_path_buf());
+    }
+    let arch = machine_name(machine);
+    let mut candidates = vec![
+        dir.join(&arch),
+        dir.join("DLLs").join(&arch),
+        dir.join("ucrt/DLLs").join(&arch),
+        dir.join("Redist/ucrt/DLLs").join(&arch),
+    ];
+    let mut versions: Vec<(FileVersion, PathBuf)> = subdirs(dir)
+        .into_iter()
+        .chain(subdirs(&dir.join("Redist")))
+        .filter_map(|d| {
+            let version = d.file_name()?.to_str()?.parse().ok()?;
+            return Some((version, d));
+        })
+        .collect();
+    versions.sort_by_key(|(version, _)| std::cmp::Reverse(*version));
+    candidates.extend(
+        versions
+            .iter()
+            .map(|(_, version_dir)| version_dir.join("ucrt/DLLs").join(&arch)),
+    );
+    return candidates.into_iter().find(has_ucrtbase);
+}
+
+/// Every dll in a UCRT redistributable dir by lower case name, they are deployed as a set.
+pub fn ucrt_redist_dlls(dir: &Path) -> Vec<(String, PathBuf)> {
+    let mut dlls: Vec<(String, PathBuf)> = match std::fs::read_dir(long_path(dir)) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .map(|e| dir.join(e.file_name()))
+            .filter(is_file)
+            .filter_map(|path| {
+                let name = path.file_name()?.to_string_lossy().to_lowercase();
+                return name.ends_with(".dll").then_some((name, path));
+            })
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    dlls.sort();
+    return dlls;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::test_dir;
+
+    fn dll(component: RuntimeComponent, version: Option<u32>, debug: bool) -> Option<RuntimeDll> {
+        return Some(RuntimeDll {
+            component,
+            version,
+            debug,
+        });
+    }
+
+    #[test]
+    fn classifies_runtime_dlls() {
+        use RuntimeComponent::*;
+        assert_eq!(
+            classify_runtime_dll("VCRUNTIME140_1.dll"),
+            dll(VcRuntime, Some(140), false)
+        );
+        assert_eq!(
+            classify_runtime_dll("vcruntime140_threadsd.dll"),
+            dll(VcRuntime, Some(140), true)
+        );
+        assert_eq!(
+            classify_runtime_dll("msvcp140_atomic_wait.dll"),
+            dll(StdCpp, Some(140), false)
+        );
+        assert_eq!(
+            classify_runtime_dll("msvcp140d_codecvt_ids.dll"),
+            dll(StdCpp, Some(140), true)
+        );
+        assert_eq!(
+            classify_runtime_dll("msvcr120.dll"),
+            dll(LegacyCrt, Some(120), false)
+        );
+        assert_eq!(
+            classify_runtime_dll("msvcrtd.dll"),
+            dll(LegacyCrt, Some(60), true)
+        );
+        assert_eq!(
+            classify_runtime_dll("msvcrt.dll"),
+            dll(SystemCrt, None, false)
+        );
+        assert_eq!(
+            classify_runtime_dll("mfcm140ud.dll"),
+            dll(Mfc, Some(140), true)
+        );
+        assert_eq!(
+            classify_runtime_dll("mfc140chs.dll"),
+            dll(MfcLocale, Some(140), false)
+        );
+        assert_eq!(
+            classify_runtime_dll("vcomp140.dll"),
+            dll(OpenMp, Some(140), false)
+        );
+        assert_eq!(classify_runtime_dll("ucrtbased.dll"), dll(Ucrt, None, true));
+        assert_eq!(
+            classify_runtime_dll("api-ms-win-crt-runtime-l1-1-0.dll"),
+            dll(UcrtForwarder, None, false)
+        );
+        assert_eq!(
+            classify_runtime_dll("api-ms-win-core-file-l1-2-0.dll"),
+            dll(ApiSet, None, false)
+        );
+    }
+
+    #[test]
+    fn tells_dlls_shipped_with_windows() {
+        let shipped = |name: &str| classify_runtime_dll(name).map(|d| d.is_shipped_with_windows());
+        assert_eq!(shipped("msvcrt.dll"), Some(true));
+        assert_eq!(shipped("MFC42.dll"), Some(true));
+        assert_eq!(shipped("mfc42u.dll"), Some(true));
+        assert_eq!(shipped("msvcp60.dll"), Some(true));
+        assert_eq!(shipped("mfc42d.dll"), Some(false));
+        assert_eq!(shipped("msvcrtd.dll"), Some(false));
+        assert_eq!(shipped("mfc70.dll"), Some(false));
+        assert_eq!(shipped("mfc140u.dll"), Some(false));
+        assert_eq!(shipped("ucrtbase.dll"), Some(false));
+    }
+
+    #[test]
+    fn ignores_other_dlls() {
+        assert_eq!(classify_runtime_dll("msvcp.dll"), None);
+        assert_eq!(classify_runtime_dll("msvcp140_foo.dll"), None);
+        assert_eq!(classify_runtime_dll("mfc140xyz.dll"), None);
+        assert_eq!(classify_runtime_dll("vcruntime140"), None);
+        assert_eq!(classify_runtime_dll("zlib1.dll"), None);
+    }
+
+    #[test]
+    fn finds_runtime_families() {
+        assert_eq!(
+            runtime_family("MSVCP140.dll"),
+            [
+                "msvcp140_1.dll",
+                "msvcp140_2.dll",
+                "msvcp140_atomic_wait.dll",
+                "msvcp140_codecvt_ids.dll"
+            ]
+        );
+        assert_eq!(
+            runtime_family("vcruntime140_threads.dll"),
+            ["vcruntime140.dll", "vcruntime140_1.dll"]
+        );
+        assert_eq!(runtime_family("mfc140u.dll"), ["mfc140.dll"]);
+        assert!(runtime_family("msvcp140d.dll").is_empty());
+        assert!(runtime_family("msvcp120.dll").is_empty());
+        assert!(runtime_family("vcomp140.dll").is_empty());
+        assert!(runtime_family("zlib1.dll").is_empty());
+    }
+
+    #[test]
+    fn finds_ucrt_redist_dirs() {
+        let kits = test_dir("ucrt-redist").join("Windows Kits/10");
+        let legacy = kits.join("Redist/ucrt/DLLs/x64");
+        let newer = kits.join("Redist/10.0.22000.0/ucrt/DLLs/x64");
+        let newest = kits.join("Redist/10.0.22621.0/ucrt/DLLs/x86");
+        for dir in [&legacy, &newer, &newest] {
+            std::fs::create_dir_all(dir).unwrap();
+            std::fs::write(dir.join("ucrtbase.dll"), b"").unwrap();
+        }
+
+        assert_eq!(ucrt_redist_dir(&kits, 0x8664), Some(legacy.clone()));
+        assert_eq!(
+            ucrt_redist_dir(&kits.join("Redist"), 0x8664),
+            Some(legacy.clone())
+        );
+        assert_eq!(
+            ucrt_redist_dir(&kits.join("Redist/ucrt/DLLs"), 0x8664),
+            Some(legacy.clone())
+        );
+        assert_eq!(ucrt_redist_dir(&newer, 0x014c), Some(newer.clone()));
+        assert_eq!(ucrt_redist_dir(&kits, 0x014c), Some(newest));
+        assert_eq!(ucrt_redist_dir(&kits, 0xaa64), None);
+
+        std::fs::remove_dir_all(kits.join("Redist/ucrt")).unwrap();
+        assert_eq!(ucrt_redist_dir(&kits, 0x8664), Some(newer));
+    }
+
+    #[test]
+    fn finds_vc_redist_dirs_of_the_machine() {
+        let msvc = test_dir("vc-redist").join("VC/Redist/MSVC");
+        let old_x64 = msvc.join("14.29.30133/x64/Microsoft.VC142.CRT");
+        let x64 = msvc.join("14.38.33130/x64/Microsoft.VC143.CRT");
+        let x86 = msvc.join("14.38.33130/x86/Microsoft.VC143.CRT");
+        let debug = msvc.join("14.38.33130/debug_nonredist/x64/Microsoft.VC143.DebugCRT");
+        for dir in [&old_x64, &x64, &x86, &debug] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+
+        assert_eq!(vc_redist_dirs(&msvc, 0x8664), [x64.clone(), old_x64]);
+        assert_eq!(vc_redist_dirs(&msvc, 0x014c), vec![x86.clone()]);
+        assert!(vc_redist_dirs(&msvc, 0xaa64).is_empty());
+        let version_dir = msvc.join("14.38.33130");
+        assert_eq!(vc_redist_dirs(&version_dir, 0x014c), [x86]);
+        assert_eq!(
+            vc_redist_dirs(&version_dir.join("x64"), 0x8664),
+            vec![x64.clone()]
+        );
+        assert!(vc_redist_dirs(&version_dir.join("x64"), 0x014c).is_empty());
+        assert_eq!(vc_redist_dirs(&x64, 0x014c), [x64]);
+    }
+}
@@ -0,0 +1,128 @@
+use crate::error::Error;
+use crate::util::long_path;
+use pelite::pe64::imports::Import;
+use pelite::PeFile;
+use std::path::Path;
+
+/// A symbol imported from a dll.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ImportedSymbol {
+    Name(String),
+    Ordinal(u16),
+}
+
+impl std::fmt::Display for ImportedSymbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            ImportedSymbol::Name(name) => write!(f, "{name}"),
+            ImportedSymbol::Ordinal(ord) => write!(f, "#{ord}"),
+        };
+    }
+}
+
+/// A dll imported by a binary, and the symbols imported from it.
+#[derive(Debug, Clone)]
+pub struct Dependency {
+    /// Lower case name of the dll
+    pub dll_name: String,
+    pub symbols: Vec<ImportedSymbol>,
+}
+
+/// Read the dlls imported by an exe or dll.
+pub fn get_dependencies(file: &Path, verbose: bool) -> Result<Vec<Dependency>, Error> {
+    let map = pelite::FileMap::open(&long_path(file))
+        .map_err(|e| Error::io(format!("Failed to read \"{}\"", file.display()), e))?;
+    let parse_error = |e: pelite::Error| Error::Parse {
+        path: file.to_path_buf(),
+        message: format!("not a valid PE file, {e}"),
+    };
+    let image = PeFile::from_bytes(&map).map_err(parse_error)?;
+
+    let imports = match image.imports() {
+        Ok(imports) => imports,
+        Err(pelite::Error::Null) => return Ok(Vec::new()),
+        Err(e) => return Err(parse_error(e)),
+    };
+    let file = file.display();
+
+    let mut ret: Vec<Dependency> = Vec::new();
+    for desc in imports {
+        let name = desc
+            .dll_name()
+            .map_err(parse_error)?
+            .to_string()
+            .to_lowercase();
+        let is_not_dll = !name.ends_with(".dll");
+        if is_not_dll && verbose {
+            println!("\"{file}\" requires \"{name}\", skipping this non-dll item.")
+        }
+        if is_not_dll {
+            continue;
+        }
+
+        let mut symbols = Vec::new();
+        match desc.int() {
+            Ok(int) => {
+                for import in int {
+                    match import {
+                        Ok(Import::ByName { name, .. }) => {
+                            symbols.push(ImportedSymbol::Name(name.to_string()))
+                        }
+                        Ok(Import::ByOrdinal { ord }) => symbols.push(ImportedSymbol::Ordinal(ord)),
+                        Err(e) if verbose => {
+                            println!(
+                                "Failed to read a symbol imported by \"{file}\" from {name}: {e}"
+                            )
+                        }
+                        Err(_) => {}
+                    }
+                }
+            }
+            Err(e) if verbose => {
+                println!("Failed to read symbols imported by \"{file}\" from {name}: {e}")
+            }
+            Err(_) => {}
+        }
+
+        // A dll may be imported by more than one descriptor
+        if let Some(dep) = ret.iter_mut().find(|d| d.dll_name == name) {
+            dep.symbols.extend(symbols);
+        } else {
+            ret.push(Dependency {
+                dll_name: name,
+                symbols,
+            });
+        }
+    }
+    return Ok(ret);
+}
+
+/// Read the machine type from PE file header.
+pub fn get_machine(file: &Path) -> Option<u16> {
+    let map = pelite::FileMap::open(&long_path(file)).ok()?;
+    let image = PeFile::from_bytes(&map).ok()?;
+    return Some(image.file_header().Machine);
+}
+
+/// Version of the linker that built the binary, like `(14, 38)` for Visual Studio 2022 17.8.
+pub fn get_linker_version(file: &Path) -> Option<(u8, u8)> {
+    let map = pelite::FileMap::open(&long_path(file)).ok()?;
+    let image = PeFile::from_bytes(&map).ok()?;
+    let version = match image.optional_header() {
+        pelite::Wrap::T32(header) => header.LinkerVersion,
+        pelite::Wrap::T64(header) => header.LinkerVersion,
+    };
+    return Some((version.Major, version.Minor));
+}
+
+pub fn machine_name(machine: u16) -> String {
+    return match machine {
+        0x014c => "x86".to_string(),
+        0x8664 => "x64".to_string(),
+        0x01c4 => "arm".to_string(),
+        0xaa64 => "arm64".to_string(),
+        0xa641 => "arm64ec".to_string(),
+        0x0200 => "ia64".to_string(),
+        other => format!("{other:#06x}"),
+    };
+}
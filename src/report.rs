@@ -0,0 +1,391 @@
+use crate::error::Error;
+use crate::msvc::runtime_family;
+use crate::pe::{get_dependencies, ImportedSymbol};
+use crate::util::{display_filename, file_size, is_file, long_path};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+/// Exes in `dir`, and dlls in `dir` keyed by lower case filename. Sub dirs are not scanned.
+pub fn binaries_in_dir(dir: &Path) -> Result<(Vec<PathBuf>, HashMap<String, PathBuf>), Error> {
+    let mut exes = Vec::new();
+    let mut dlls = HashMap::new();
+    let entries = std::fs::read_dir(long_path(dir))
+        .map_err(|e| Error::io(format!("Failed to scan \"{}\"", dir.display()), e))?;
+    for entry in entries.flatten() {
+        let path = dir.join(entry.file_name());
+        if !is_file(&path) {
+            continue;
+        }
+        let filename = match path.file_name() {
+            Some(n) => n.to_string_lossy().to_lowercase(),
+            None => continue,
+        };
+        if filename.ends_with(".exe") {
+            exes.push(path);
+        } else if filename.ends_with(".dll") {
+            dlls.insert(filename, path);
+        }
+    }
+    return Ok((exes, dlls));
+}
+
+/// A dll that is not loaded by any binary.
+#[derive(Debug, Clone)]
+pub struct OrphanDll {
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+/// Find dlls in `dir` that can't be reached from any exe in that dir or any of `roots`. Dlls of
+/// the VC redistributable deployed as a set with a reachable one, like `msvcp140_1.dll` with
+/// `msvcp140.dll`, are reachable too.
+pub fn audit_orphans(dir: &Path, roots: &[PathBuf]) -> Result<Vec<OrphanDll>, Error> {
+    let (mut pending, dlls_in_dir) = binaries_in_dir(dir)?;
+    pending.extend(roots.iter().cloned());
+
+    let mut reachable: HashSet<String> = HashSet::new();
+    for root in &pending {
+        if root.parent() != Some(dir) {
+            continue;
+        }
+        if let Some(name) = root.file_name() {
+            reachable.insert(name.to_string_lossy().to_lowercase());
+        }
+    }
+
+    let mut visited: HashSet<PathBuf> = HashSet::new();
+    while let Some(binary) = pending.pop() {
+        if !visited.insert(binary.clone()) || !is_file(&binary) {
+            continue;
+        }
+        for dep in get_dependencies(&binary, false)? {
+            let family = runtime_family(&dep.dll_name);
+            for dep in std::iter::once(dep.dll_name).chain(family) {
+                if let Some(dll) = dlls_in_dir.get(&dep) {
+                    pending.push(dll.clone());
+                    reachable.insert(dep);
+                }
+            }
+        }
+    }
+
+    let mut orphans: Vec<OrphanDll> = dlls_in_dir
+        .iter()
+        .filter(|(name, _)| !reachable.contains(*name))
+        .map(|(_, path)| OrphanDll {
+            path: path.clone(),
+            size: file_size(path),
+        })
+        .collect();
+    orphans.sort_by(|a, b| a.path.cmp(&b.path));
+    return Ok(orphans);
+}
+
+/// A dll required by a root, and the shortest importer chain that requires it.
+#[derive(Debug, Clone)]
+pub struct SizeEntry {
+    pub dll: PathBuf,
+    pub size: u64,
+    /// Filenames of importers, from the direct importer to the root
+    pub chain: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RootSize {
+    pub root: PathBuf,
+    /// Size of the root and all dlls it requires
+    pub total: u64,
+    /// Sorted by size, the largest first
+    pub dlls: Vec<SizeEntry>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SizeReport {
+    pub roots: Vec<RootSize>,
+    /// Size of roots and the dlls they require, every file is counted once
+    pub total: u64,
+}
+
+/// Attribute the size of every dll in `dir` to each of `roots` that requires it.
+pub fn size_report(dir: &Path, roots: &[PathBuf]) -> Result<SizeReport, Error> {
+    let (_, dlls_in_dir) = binaries_in_dir(dir)?;
+    let mut roots = roots.to_vec();
+    roots.sort();
+    roots.dedup();
+
+    let mut counted: HashSet<PathBuf> = HashSet::new();
+    let mut report = SizeReport::default();
+    for root in roots {
+        // dll -> importer chain from the direct importer to the root, found by BFS so that the
+        // shortest chain is kept
+        let mut chains: HashMap<String, Vec<String>> = HashMap::new();
+        let mut pending = VecDeque::from([(root.clone(), vec![display_filename(&root)])]);
+        while let Some((binary, chain)) = pending.pop_front() {
+            for dep in get_dependencies(&binary, false)? {
+                let dep = dep.dll_name;
+                let dll = match dlls_in_dir.get(&dep) {
+                    Some(d) => d,
+                    None => continue,
+                };
+                if *dll == root || chains.contains_key(&dep) {
+                    continue;
+                }
+                let mut next_chain = vec![display_filename(dll)];
+                next_chain.extend(chain.iter().cloned());
+                chains.insert(dep, chain.clone());
+                pending.push_back((dll.clone(), next_chain));
+            }
+        }
+
+        let mut root_size = RootSize {
+            total: file_size(&root),
+            root: root.clone(),
+            dlls: Vec::with_capacity(chains.len()),
+        };
+        if counted.insert(root) {
+            report.total += root_size.total;
+        }
+        for (dep, chain) in chains {
+            let dll = &dlls_in_dir[&dep];
+            let size = file_size(dll);
+            root_size.total += size;
+            if counted.insert(dll.clone()) {
+                report.total += size;
+            }
+            root_size.dlls.push(SizeEntry {
+                dll: dll.clone(),
+                size,
+                chain,
+            });
+        }
+        root_size
+            .dlls
+            .sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.dll.cmp(&b.dll)));
+        report.roots.push(root_size);
+    }
+    return Ok(report);
+}
+
+/// Symbols imported along an edge of the dependency graph.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ImportEdge {
+    /// Path of the importer, relative to the deployed dir if possible
+    pub importer: String,
+    /// Path of the dll, relative to the deployed dir
+    pub dll: String,
+    /// Sorted and deduplicated
+    pub symbols: Vec<ImportedSymbol>,
+}
+
+/// Collect the symbols imported along every edge from `roots` and the dlls they require to the
+/// dlls in `dir`.
+pub fn import_report(dir: &Path, roots: &[PathBuf]) -> Result<Vec<ImportEdge>, Error> {
+    let (_, dlls_in_dir) = binaries_in_dir(dir)?;
+    let relative = |path: &Path| -> String {
+        return match path.strip_prefix(dir) {
+            Ok(p) => p.display().to_string(),
+            Err(_) => path.display().to_string(),
+        };
+    };
+
+    let mut pending: Vec<PathBuf> = roots.to_vec();
+    let mut visited: HashSet<PathBuf> = HashSet::new();
+    let mut edges: Vec<ImportEdge> = Vec::new();
+    while let Some(binary) = pending.pop() {
+        if !visited.insert(binary.clone()) || !is_file(&binary) {
+            continue;
+        }
+        for dep in get_dependencies(&binary, false)? {
+            let dll = match dlls_in_dir.get(&dep.dll_name) {
+                Some(d) => d,
+                None => continue,
+            };
+            pending.push(dll.clone());
+            let mut symbols = dep.symbols;
+            symbols.sort();
+            symbols.dedup();
+            edges.push(ImportEdge {
+                importer: relative(&binary),
+                dll: relative(dll),
+                symbols,
+            });
+        }
+    }
+    edges.sort();
+    return Ok(edges);
+}
+
+/// Fails if the total size of the deployed files exceeds `budget`.
+pub fn check_size_budget(total: u64, budget: u64) -> Result<(), Error> {
+    if total > budget {
+        return Err(Error::SizeBudgetExceeded { total, budget });
+    }
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_pe::TestPe;
+    use crate::util::test_dir;
+
+    /// A deployed dir of the graph below, with a plugin outside of it. Every file is padded to a
+    /// distinct size.
+    ///
+    /// ```text
+    /// app.exe -> a.dll -> c.dll
+    ///         -> b.dll
+    /// tool.exe -> b.dll
+    /// d.dll -> e.dll
+    /// ../plugins/p.dll -> f.dll
+    /// ```
+    fn synthetic_graph(name: &str) -> PathBuf {
+        let root = test_dir(name);
+        let dir = root.join("bin");
+        let files = [
+            (
+                "app.exe",
+                60_000,
+                TestPe::exe()
+                    .import("a.dll", &["foo", "#3"])
+                    .import("b.dll", &["bar"]),
+            ),
+            (
+                "tool.exe",
+                50_000,
+                TestPe::exe().import("b.dll", &["baz", "bar", "bar"]),
+            ),
+            ("a.dll", 30_000, TestPe::dll().import("c.dll", &["qux"])),
+            ("b.dll", 20_000, TestPe::dll()),
+            ("c.dll", 40_000, TestPe::dll()),
+            ("d.dll", 11_000, TestPe::dll().import("e.dll", &[])),
+            ("e.dll", 12_000, TestPe::dll()),
+            ("f.dll", 13_000, TestPe::dll()),
+            (
+                "../plugins/p.dll",
+                14_000,
+                TestPe::dll().import("f.dll", &["f"]),
+            ),
+        ];
+        for (file, size, pe) in files {
+            let path = dir.join(file);
+            pe.write(&path);
+            let file = std::fs::OpenOptions::new().write(true).open(&path).unwrap();
+            assert!(file.metadata().unwrap().len() <= size);
+            file.set_len(size).unwrap();
+        }
+        return dir;
+    }
+
+    #[test]
+    fn audits_orphans_of_synthetic_graph() {
+        let dir = synthetic_graph("report-orphans");
+        let orphans = |roots: &[PathBuf]| -> Vec<(String, u64)> {
+            return audit_orphans(&dir, roots)
+                .unwrap()
+                .iter()
+                .map(|o| (display_filename(&o.path), o.size))
+                .collect();
+        };
+        let golden = |names: &[(&str, u64)]| -> Vec<(String, u64)> {
+            return names.iter().map(|(n, s)| (n.to_string(), *s)).collect();
+        };
+        assert_eq!(
+            orphans(&[]),
+            golden(&[("d.dll", 11_000), ("e.dll", 12_000), ("f.dll", 13_000)])
+        );
+        let plugin = dir.join("../plugins/p.dll");
+        assert_eq!(
+            orphans(&[plugin]),
+            golden(&[("d.dll", 11_000), ("e.dll", 12_000)])
+        );
+    }
+
+    #[test]
+    fn attributes_sizes_of_synthetic_graph() {
+        let dir = synthetic_graph("report-sizes");
+        let roots = [
+            dir.join("tool.exe"),
+            dir.join("app.exe"),
+            dir.join("app.exe"),
+        ];
+        let report = size_report(&dir, &roots).unwrap();
+        let mut text = String::new();
+        for root in &report.roots {
+            text += &format!("{} {}\n", display_filename(&root.root), root.total);
+            for dll in &root.dlls {
+                let dll_name = display_filename(&dll.dll);
+                let chain = dll.chain.join(" <- ");
+                text += &format!("  {dll_name} {} <- {chain}\n", dll.size);
+            }
+        }
+        let golden = "\
+app.exe 150000
+  c.dll 40000 <- a.dll <- app.exe
+  a.dll 30000 <- app.exe
+  b.dll 20000 <- app.exe
+tool.exe 70000
+  b.dll 20000 <- tool.exe
+";
+        assert_eq!(text, golden);
+        // b.dll is counted once
+        assert_eq!(report.total, 200_000);
+
+        assert!(check_size_budget(report.total, 200_000).is_ok());
+        let exceeded = check_size_budget(report.total, 199_999).unwrap_err();
+        assert!(matches!(
+            exceeded,
+            Error::SizeBudgetExceeded {
+                total: 200_000,
+                budget: 199_999
+            }
+        ));
+        assert_eq!(exceeded.exit_code(), 6);
+    }
+
+    #[test]
+    fn reports_imports_of_synthetic_graph() {
+        let dir = synthetic_graph("report-imports");
+        let plugin = dir.join("../plugins/p.dll");
+        let roots = [dir.join("app.exe"), dir.join("tool.exe"), plugin];
+        let edges = import_report(&dir, &roots).unwrap();
+        let edges: Vec<(String, String, Vec<String>)> = edges
+            .iter()
+            .map(|e| {
+                let symbols = e.symbols.iter().map(|s| s.to_string()).collect();
+                return (e.importer.clone(), e.dll.clone(), symbols);
+            })
+            .collect();
+        let edge = |importer: &str, dll: &str, symbols: &[&str]| {
+            let symbols = symbols.iter().map(|s| s.to_string()).collect();
+            return (importer.to_string(), dll.to_string(), symbols);
+        };
+        // Symbols are sorted and deduplicated, names before ordinals
+        assert_eq!(
+            edges,
+            [
+                edge("../plugins/p.dll", "f.dll", &["f"]),
+                edge("a.dll", "c.dll", &["qux"]),
+                edge("app.exe", "a.dll", &["foo", "#3"]),
+                edge("app.exe", "b.dll", &["bar"]),
+                edge("tool.exe", "b.dll", &["bar", "baz"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_runtime_family_members_in_orphan_audit() {
+        let dir = test_dir("report-orphan-family");
+        TestPe::exe()
+            .import("msvcp140.dll", &[])
+            .write(&dir.join("app.exe"));
+        for name in ["msvcp140.dll", "msvcp140_1.dll", "msvcp140_atomic_wait.dll"] {
+            TestPe::dll().write(&dir.join(name));
+        }
+        TestPe::dll().write(&dir.join("vcruntime140_1.dll"));
+
+        let orphans = audit_orphans(&dir, &[]).unwrap();
+        let orphans: Vec<&Path> = orphans.iter().map(|o| o.path.as_path()).collect();
+        assert_eq!(orphans, [dir.join("vcruntime140_1.dll")]);
+    }
+}
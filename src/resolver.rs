@@ -0,0 +1,646 @@
+use crate::cmake::{imported_dlls, prefix_dll_dirs, ImportedDll};
+use crate::msvc::{vc_redist_dirs, REDIST_MACHINES};
+use crate::pe::machine_name;
+use crate::search::{
+    existing_search_var, existing_var_path, search_dll_deep, search_dll_shallow,
+    shallow_candidates, validate_dll,
+};
+use crate::toolchain::toolchain_runtime_dirs;
+use crate::vcpkg::VcpkgPrefix;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Extra check on a found dll, returns the reason if the dll should be skipped.
+pub type DllValidator<'a> = Option<&'a dyn Fn(&Path) -> Result<(), String>>;
+
+/// Build configuration of a binary, guessed from the C runtime it imports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BuildConfig {
+    Release,
+    Debug,
+}
+
+impl std::fmt::Display for BuildConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            BuildConfig::Release => write!(f, "release"),
+            BuildConfig::Debug => write!(f, "debug"),
+        };
+    }
+}
+
+/// A source of dlls. Resolvers are tried in order until one of them finds the dll.
+pub trait Resolver: Send + Sync {
+    /// Recorded in the plan for every dll this resolver finds
+    fn name(&self) -> &str;
+
+    /// Find a file named `dll_name`, skipping files rejected by `validate`.
+    fn resolve(&self, dll_name: &str, validate: DllValidator) -> Option<PathBuf>;
+
+    /// Every file named `dll_name` this resolver can find, used to detect ambiguous candidates.
+    fn candidates(&self, dll_name: &str, validate: DllValidator) -> Vec<PathBuf> {
+        return self.resolve(dll_name, validate).into_iter().collect();
+    }
+
+    /// Find `dll_name` for an importer of `config` and `machine`. Only resolvers that have dlls
+    /// of several configs or architectures need to implement it, others ignore them.
+    fn resolve_for(
+        &self,
+        dll_name: &str,
+        config: Option<BuildConfig>,
+        machine: Option<u16>,
+        validate: DllValidator,
+    ) -> Option<PathBuf> {
+        let _ = (config, machine);
+        return self.resolve(dll_name, validate);
+    }
+
+    /// Every file named `dll_name` this resolver can find for an importer of `config` and
+    /// `machine`.
+    fn candidates_for(
+        &self,
+        dll_name: &str,
+        config: Option<BuildConfig>,
+        machine: Option<u16>,
+        validate: DllValidator,
+    ) -> Vec<PathBuf> {
+        let _ = (config, machine);
+        return self.candidates(dll_name, validate);
+    }
+}
+
+impl std::fmt::Debug for dyn Resolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "Resolver({})", self.name());
+    }
+}
+
+/// Built-in resolvers, used to configure the resolver order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResolverKind {
+    /// Search in the VC redistributable dirs of a Visual Studio installation
+    VcRedist,
+    /// Search in shallow search dirs
+    Shallow,
+    /// Search in the runtime dirs of a MinGW, llvm-mingw or MSYS2 toolchain
+    Toolchain,
+    /// Search in CMake prefixes installed by vcpkg, in `bin` or `debug/bin` for the config of
+    /// the importer
+    Vcpkg,
+    /// Search in `<prefix>/bin` of every CMake prefix
+    CmakePrefix,
+    /// Search in system variable PATH, only on Windows
+    EnvPath,
+    /// Search in PATH-like environment variables, like `DEPLOY_DLL_PATH` and `WINEPATH`
+    EnvVar,
+    /// Search recursively in deep search dirs
+    Deep,
+    /// Search recursively in the build dir of the CMake cache, a fallback for dlls built outside
+    /// the runtime output dir
+    BuildDir,
+    /// Resolvers added by [`crate::Deployer::resolver`], in the order they are added
+    Custom,
+}
+
+impl ResolverKind {
+    pub const DEFAULT_ORDER: [ResolverKind; 10] = [
+        ResolverKind::VcRedist,
+        ResolverKind::Shallow,
+        ResolverKind::Toolchain,
+        ResolverKind::Vcpkg,
+        ResolverKind::CmakePrefix,
+        ResolverKind::EnvPath,
+        ResolverKind::EnvVar,
+        ResolverKind::Custom,
+        ResolverKind::Deep,
+        ResolverKind::BuildDir,
+    ];
+
+    pub fn name(self) -> &'static str {
+        return match self {
+            ResolverKind::VcRedist => "vc-redist",
+            ResolverKind::Shallow => "shallow",
+            ResolverKind::Toolchain => "toolchain",
+            ResolverKind::Vcpkg => "vcpkg",
+            ResolverKind::CmakePrefix => "cmake-prefix",
+            ResolverKind::EnvPath => "path",
+            ResolverKind::EnvVar => "env-var",
+            ResolverKind::Deep => "deep",
+            ResolverKind::BuildDir => "build-dir",
+            ResolverKind::Custom => "custom",
+        };
+    }
+}
+
+impl std::fmt::Display for ResolverKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "{}", self.name());
+    }
+}
+
+impl FromStr for ResolverKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        for kind in ResolverKind::DEFAULT_ORDER {
+            if kind.name() == s {
+                return Ok(kind);
+            }
+        }
+        let names: Vec<&str> = ResolverKind::DEFAULT_ORDER
+            .iter()
+            .map(|k| k.name())
+            .collect();
+        return Err(format!(
+            "Unknown resolver \"{s}\", expected one of {}",
+            names.join(", ")
+        ));
+    }
+}
+
+/// Search for dlls directly in some dirs.
+#[derive(Debug, Clone)]
+pub struct DirResolver {
+    name: String,
+    dirs: Vec<PathBuf>,
+    verbose: bool,
+}
+
+impl DirResolver {
+    pub fn new(name: impl Into<String>, dirs: Vec<PathBuf>, verbose: bool) -> DirResolver {
+        return DirResolver {
+            name: name.into(),
+            dirs,
+            verbose,
+        };
+    }
+
+    /// Search in the runtime dirs of the toolchain of a target triple or a compiler.
+    pub fn toolchain(triple: Option<&str>, compiler: Option<&Path>, verbose: bool) -> DirResolver {
+        let dirs = toolchain_runtime_dirs(triple, compiler);
+        if verbose {
+            println!("Found toolchain runtime dirs {dirs:?}");
+        }
+        return DirResolver::new(ResolverKind::Toolchain.name(), dirs, verbose);
+    }
+
+    /// Search in dirs of system variable PATH.
+    pub fn env_path(verbose: bool) -> DirResolver {
+        let mut dirs = Vec::new();
+        existing_var_path(&mut dirs);
+        return DirResolver::new(ResolverKind::EnvPath.name(), dirs, verbose);
+    }
+
+    /// Search in dirs of PATH-like environment variables.
+    pub fn env_vars(names: &[String], verbose: bool) -> DirResolver {
+        let mut dirs = Vec::new();
+        for name in names {
+            existing_search_var(name, &mut dirs);
+        }
+        return DirResolver::new(ResolverKind::EnvVar.name(), dirs, verbose);
+    }
+}
+
+impl Resolver for DirResolver {
+    fn name(&self) -> &str {
+        return &self.name;
+    }
+
+    fn resolve(&self, dll_name: &str, validate: DllValidator) -> Option<PathBuf> {
+        return search_dll_shallow(dll_name, &self.dirs, self.verbose, validate);
+    }
+
+    fn candidates(&self, dll_name: &str, validate: DllValidator) -> Vec<PathBuf> {
+        return shallow_candidates(dll_name, &self.dirs, self.verbose, validate);
+    }
+}
+
+/// Search for dlls in the VC redistributable dirs of the architecture of the importer, newest
+/// toolset first.
+#[derive(Debug, Clone)]
+pub struct VcRedistResolver {
+    dirs: Vec<(u16, Vec<PathBuf>)>,
+    verbose: bool,
+}
+
+impl VcRedistResolver {
+    pub fn new(dir: &Path, verbose: bool) -> VcRedistResolver {
+        let dirs: Vec<(u16, Vec<PathBuf>)> = REDIST_MACHINES
+            .iter()
+            .map(|machine| (*machine, vc_redist_dirs(dir, *machine)))
+            .collect();
+        if verbose {
+            for (machine, dirs) in &dirs {
+                println!(
+                    "Found {} VC redistributable dir(s) for {} in \"{}\"",
+                    dirs.len(),
+                    machine_name(*machine),
+                    dir.display()
+                );
+            }
+        }
+        return VcRedistResolver { dirs, verbose };
+    }
+
+    /// Dirs of `machine`, or of every architecture if it is unknown
+    fn dirs(&self, machine: Option<u16>) -> Vec<PathBuf> {
+        return self
+            .dirs
+            .iter()
+            .filter(|(m, _)| machine.is_none() || machine == Some(*m))
+            .flat_map(|(_, dirs)| dirs.iter().cloned())
+            .collect();
+    }
+}
+
+impl Resolver for VcRedistResolver {
+    fn name(&self) -> &str {
+        return ResolverKind::VcRedist.name();
+    }
+
+    fn resolve(&self, dll_name: &str, validate: DllValidator) -> Option<PathBuf> {
+        return self.resolve_for(dll_name, None, None, validate);
+    }
+
+    fn candidates(&self, dll_name: &str, validate: DllValidator) -> Vec<PathBuf> {
+        return self.candidates_for(dll_name, None, None, validate);
+    }
+
+    fn resolve_for(
+        &self,
+        dll_name: &str,
+        _config: Option<BuildConfig>,
+        machine: Option<u16>,
+        validate: DllValidator,
+    ) -> Option<PathBuf> {
+        return search_dll_shallow(dll_name, &self.dirs(machine), self.verbose, validate);
+    }
+
+    fn candidates_for(
+        &self,
+        dll_name: &str,
+        _config: Option<BuildConfig>,
+        machine: Option<u16>,
+        validate: DllValidator,
+    ) -> Vec<PathBuf> {
+        return shallow_candidates(dll_name, &self.dirs(machine), self.verbose, validate);
+    }
+}
+
+/// Search for dlls in triplet dirs of vcpkg, in the dirs of the config of the importer first.
+#[derive(Debug, Clone)]
+pub struct VcpkgResolver {
+    release_dirs: Vec<PathBuf>,
+    debug_dirs: Vec<PathBuf>,
+    verbose: bool,
+}
+
+impl VcpkgResolver {
+    pub fn new(prefixes: &[VcpkgPrefix], verbose: bool) -> VcpkgResolver {
+        let dirs = |config| -> Vec<PathBuf> {
+            return prefixes.iter().flat_map(|p| p.dll_dirs(config)).collect();
+        };
+        if verbose {
+            for prefix in prefixes {
+                println!(
+                    "Found vcpkg triplet {} with {} port(s) in \"{}\"",
+                    prefix.triplet,
+                    prefix.ports().len(),
+                    prefix.prefix.display()
+                );
+            }
+        }
+        return VcpkgResolver {
+            release_dirs: dirs(BuildConfig::Release),
+            debug_dirs: dirs(BuildConfig::Debug),
+            verbose,
+        };
+    }
+
+    fn dirs(&self, config: Option<BuildConfig>) -> &[PathBuf] {
+        return match config {
+            Some(BuildConfig::Debug) => &self.debug_dirs,
+            _ => &self.release_dirs,
+        };
+    }
+}
+
+impl Resolver for VcpkgResolver {
+    fn name(&self) -> &str {
+        return ResolverKind::Vcpkg.name();
+    }
+
+    fn resolve(&self, dll_name: &str, validate: DllValidator) -> Option<PathBuf> {
+        return self.resolve_for(dll_name, None, None, validate);
+    }
+
+    fn candidates(&self, dll_name: &str, validate: DllValidator) -> Vec<PathBuf> {
+        return self.candidates_for(dll_name, None, None, validate);
+    }
+
+    fn resolve_for(
+        &self,
+        dll_name: &str,
+        config: Option<BuildConfig>,
+        _machine: Option<u16>,
+        validate: DllValidator,
+    ) -> Option<PathBuf> {
+        return search_dll_shallow(dll_name, self.dirs(config), self.verbose, validate);
+    }
+
+    fn candidates_for(
+        &self,
+        dll_name: &str,
+        config: Option<BuildConfig>,
+        _machine: Option<u16>,
+        validate: DllValidator,
+    ) -> Vec<PathBuf> {
+        return shallow_candidates(dll_name, self.dirs(config), self.verbose, validate);
+    }
+}
+
+/// Search for dlls in CMake prefixes, at the locations of imported targets exported by packages
+/// first, then in the dll dirs of the prefixes. Dlls of the config of the importer come first.
+#[derive(Debug, Clone)]
+pub struct CmakePrefixResolver {
+    imported: Vec<ImportedDll>,
+    release_dirs: Vec<PathBuf>,
+    debug_dirs: Vec<PathBuf>,
+    verbose: bool,
+}
+
+impl CmakePrefixResolver {
+    pub fn new(prefixes: &[PathBuf], verbose: bool) -> CmakePrefixResolver {
+        let dirs = |config| -> Vec<PathBuf> {
+            let mut dirs: Vec<PathBuf> = Vec::new();
+            for dir in prefixes.iter().flat_map(|p| prefix_dll_dirs(p, config)) {
+                if !dirs.contains(&dir) {
+                    dirs.push(dir);
+                }
+            }
+            return dirs;
+        };
+        let mut imported = Vec::new();
+        for prefix in prefixes {
+            let dlls = imported_dlls(prefix);
+            if verbose && !dlls.is_empty() {
+                println!(
+                    "Found {} dll(s) of imported targets in \"{}\"",
+                    dlls.len(),
+                    prefix.display()
+                );
+            }
+            imported.extend(dlls);
+        }
+        return CmakePrefixResolver {
+            imported,
+            release_dirs: dirs(BuildConfig::Release),
+            debug_dirs: dirs(BuildConfig::Debug),
+            verbose,
+        };
+    }
+
+    fn dirs(&self, config: Option<BuildConfig>) -> &[PathBuf] {
+        return match config {
+            Some(BuildConfig::Debug) => &self.debug_dirs,
+            _ => &self.release_dirs,
+        };
+    }
+
+    /// Locations of imported targets named `dll_name`, the ones of `config` first
+    fn imported_candidates(
+        &self,
+        dll_name: &str,
+        config: Option<BuildConfig>,
+        validate: DllValidator,
+    ) -> Vec<PathBuf> {
+        let config = config.unwrap_or(BuildConfig::Release);
+        let mut imported: Vec<&ImportedDll> = self
+            .imported
+            .iter()
+            .filter(|dll| {
+                return dll
+                    .path
+                    .file_name()
+                    .is_some_and(|name| name.eq_ignore_ascii_case(dll_name));
+            })
+            .collect();
+        imported.sort_by_key(|dll| dll.build_config() != config);
+        let mut candidates: Vec<PathBuf> = Vec::new();
+        for dll in imported {
+            if !candidates.contains(&dll.path) && validate_dll(&dll.path, self.verbose, validate) {
+                candidates.push(dll.path.clone());
+            }
+        }
+        return candidates;
+    }
+}
+
+impl Resolver for CmakePrefixResolver {
+    fn name(&self) -> &str {
+        return ResolverKind::CmakePrefix.name();
+    }
+
+    fn resolve(&self, dll_name: &str, validate: DllValidator) -> Option<PathBuf> {
+        return self.resolve_for(dll_name, None, None, validate);
+    }
+
+    fn candidates(&self, dll_name: &str, validate: DllValidator) -> Vec<PathBuf> {
+        return self.candidates_for(dll_name, None, None, validate);
+    }
+
+    fn resolve_for(
+        &self,
+        dll_name: &str,
+        config: Option<BuildConfig>,
+        _machine: Option<u16>,
+        validate: DllValidator,
+    ) -> Option<PathBuf> {
+        if let Some(path) = self.imported_candidates(dll_name, config, validate).first() {
+            if self.verbose {
+                println!(
+                    "Found \"{}\" at the location of an imported target",
+                    path.display()
+                );
+            }
+            return Some(path.clone());
+        }
+        return search_dll_shallow(dll_name, self.dirs(config), self.verbose, validate);
+    }
+
+    fn candidates_for(
+        &self,
+        dll_name: &str,
+        config: Option<BuildConfig>,
+        _machine: Option<u16>,
+        validate: DllValidator,
+    ) -> Vec<PathBuf> {
+        let mut candidates = self.imported_candidates(dll_name, config, validate);
+        for path in shallow_candidates(dll_name, self.dirs(config), self.verbose, validate) {
+            if !candidates.contains(&path) {
+                candidates.push(path);
+            }
+        }
+        return candidates;
+    }
+}
+
+/// Search for dlls recursively in some dirs.
+#[derive(Debug, Clone)]
+pub struct DeepDirResolver {
+    name: &'static str,
+    dirs: Vec<PathBuf>,
+    verbose: bool,
+}
+
+impl DeepDirResolver {
+    pub fn new(dirs: Vec<PathBuf>, verbose: bool) -> DeepDirResolver {
+        return DeepDirResolver {
+            name: ResolverKind::Deep.name(),
+            dirs,
+            verbose,
+        };
+    }
+
+    /// Search build dirs of CMake
+    pub fn build_dirs(dirs: Vec<PathBuf>, verbose: bool) -> DeepDirResolver {
+        return DeepDirResolver {
+            name: ResolverKind::BuildDir.name(),
+            dirs,
+            verbose,
+        };
+    }
+}
+
+impl Resolver for DeepDirResolver {
+    fn name(&self) -> &str {
+        return self.name;
+    }
+
+    fn resolve(&self, dll_name: &str, validate: DllValidator) -> Option<PathBuf> {
+        return search_dll_deep(dll_name, &self.dirs, self.verbose, validate);
+    }
+
+    /// Walking the dirs again for every dll is too slow, so deep search dirs are not checked for
+    /// ambiguous candidates.
+    fn candidates(&self, dll_name: &str, validate: DllValidator) -> Vec<PathBuf> {
+        let _ = (dll_name, validate);
+        return Vec::new();
+    }
+}
+
+/// A resolver backed by a closure that maps a dll name to a file.
+pub struct FnResolver<F> {
+    name: String,
+    resolve: F,
+}
+
+impl<F> FnResolver<F>
+where
+    F: Fn(&str) -> Option<PathBuf> + Send + Sync,
+{
+    pub fn new(name: impl Into<String>, resolve: F) -> FnResolver<F> {
+        return FnResolver {
+            name: name.into(),
+            resolve,
+        };
+    }
+}
+
+impl<F> Resolver for FnResolver<F>
+where
+    F: Fn(&str) -> Option<PathBuf> + Send + Sync,
+{
+    fn name(&self) -> &str {
+        return &self.name;
+    }
+
+    fn resolve(&self, dll_name: &str, validate: DllValidator) -> Option<PathBuf> {
+        let location = (self.resolve)(dll_name)?;
+        if !validate_dll(&location, false, validate) {
+            return None;
+        }
+        return Some(location);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::test_dir;
+
+    fn touch(path: &Path) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, "").unwrap();
+    }
+
+    #[test]
+    fn resolves_vcpkg_dlls_of_the_config() {
+        let dir = test_dir("resolver-vcpkg");
+        let triplet = dir.join("installed/x64-windows");
+        touch(&dir.join("installed/vcpkg/status"));
+        let release = triplet.join("bin/fmt.dll");
+        let debug = triplet.join("debug/bin/fmt.dll");
+        let release_only = triplet.join("bin/zlib1.dll");
+        for file in [&release, &debug, &release_only] {
+            touch(file);
+        }
+        let resolver = VcpkgResolver::new(&[VcpkgPrefix::detect(&triplet).unwrap()], false);
+
+        let resolve = |config| resolver.resolve_for("fmt.dll", config, None, None);
+        assert_eq!(resolve(Some(BuildConfig::Debug)), Some(debug.clone()));
+        assert_eq!(resolve(Some(BuildConfig::Release)), Some(release.clone()));
+        assert_eq!(resolve(None), Some(release.clone()));
+        assert_eq!(
+            resolver.candidates_for("fmt.dll", Some(BuildConfig::Debug), None, None),
+            [debug, release]
+        );
+        // A dll only built in one config is still found
+        assert_eq!(
+            resolver.resolve_for("zlib1.dll", Some(BuildConfig::Debug), None, None),
+            Some(release_only)
+        );
+    }
+
+    #[test]
+    fn resolves_imported_locations_of_the_config() {
+        let dir = test_dir("resolver-cmake-prefix");
+        let prefix = dir.join("prefix");
+        let config_dir = prefix.join("lib/cmake/fmt");
+        std::fs::create_dir_all(&config_dir).unwrap();
+        for (config, dir) in [("RELEASE", "r"), ("DEBUG", "d")] {
+            std::fs::write(
+                config_dir.join(format!("fmt-targets-{}.cmake", config.to_lowercase())),
+                format!(
+                    "set_target_properties(fmt::fmt PROPERTIES\n  \
+                     IMPORTED_LOCATION_{config} \"${{_IMPORT_PREFIX}}/out/{dir}/fmt.dll\"\n)\n"
+                ),
+            )
+            .unwrap();
+        }
+        let release = prefix.join("out/r/fmt.dll");
+        let debug = prefix.join("out/d/fmt.dll");
+        let release_dir = prefix.join("bin/Release/foo.dll");
+        let debug_dir = prefix.join("bin/Debug/foo.dll");
+        for file in [&release, &debug, &release_dir, &debug_dir] {
+            touch(file);
+        }
+        let resolver = CmakePrefixResolver::new(&[prefix], false);
+
+        let resolve = |name, config| resolver.resolve_for(name, config, None, None);
+        assert_eq!(resolve("fmt.dll", Some(BuildConfig::Debug)), Some(debug));
+        assert_eq!(
+            resolve("fmt.dll", Some(BuildConfig::Release)),
+            Some(release.clone())
+        );
+        assert_eq!(resolve("fmt.dll", None), Some(release));
+        assert_eq!(
+            resolve("foo.dll", Some(BuildConfig::Debug)),
+            Some(debug_dir)
+        );
+        assert_eq!(
+            resolve("foo.dll", Some(BuildConfig::Release)),
+            Some(release_dir)
+        );
+    }
+}
@@ -0,0 +1,269 @@
+use crate::deployer::TargetGroup;
+use crate::error::Error;
+use crate::pe::{get_dependencies, get_machine};
+use crate::util::{can_be_dir, is_file, walk_dir};
+use glob::{MatchOptions, Pattern};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Exes and dlls found under an install prefix.
+#[derive(Debug, Clone, Default)]
+pub struct InstallTree {
+    pub prefix: PathBuf,
+    pub executables: Vec<PathBuf>,
+    pub dlls: Vec<PathBuf>,
+    /// Files named like exes or dlls that are not valid PE files
+    pub skipped: Vec<PathBuf>,
+}
+
+impl InstallTree {
+    /// Find every exe and dll under `prefix` recursively. Globs are matched against paths
+    /// relative to `prefix` separated by `/`, ignoring case. Files matching none of `include` are
+    /// skipped unless `include` is empty, files matching any of `exclude` are skipped. Files that
+    /// are not valid PE files are listed in [`InstallTree::skipped`].
+    pub fn scan(
+        prefix: &Path,
+        include: &[String],
+        exclude: &[String],
+    ) -> Result<InstallTree, Error> {
+        if !can_be_dir(&prefix) {
+            return Err(Error::config(format!(
+                "Install prefix \"{}\" is not a dir",
+                prefix.display()
+            )));
+        }
+        let include = compile_globs(include)?;
+        let exclude = compile_globs(exclude)?;
+        let options = MatchOptions {
+            case_sensitive: false,
+            require_literal_separator: true,
+            require_literal_leading_dot: false,
+        };
+
+        let mut tree = InstallTree {
+            prefix: prefix.to_path_buf(),
+            ..InstallTree::default()
+        };
+        for entry in walk_dir(prefix, WalkDir::sort_by_file_name) {
+            let (path, _) = entry.map_err(|e| {
+                return Error::io(format!("Failed to scan \"{}\"", prefix.display()), e.into());
+            })?;
+            let path = path.as_path();
+            let extension = path
+                .extension()
+                .map(|e| e.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+            if (extension != "exe" && extension != "dll") || !is_file(&path) {
+                continue;
+            }
+            let relative_path = path
+                .strip_prefix(prefix)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            if !include.is_empty()
+                && !include
+                    .iter()
+                    .any(|p| p.matches_with(&relative_path, options))
+            {
+                continue;
+            }
+            if exclude
+                .iter()
+                .any(|p| p.matches_with(&relative_path, options))
+            {
+                continue;
+            }
+            if get_machine(path).is_none() {
+                tree.skipped.push(path.to_path_buf());
+            } else if extension == "exe" {
+                tree.executables.push(path.to_path_buf());
+            } else {
+                tree.dlls.push(path.to_path_buf());
+            }
+        }
+        return Ok(tree);
+    }
+
+    /// Exes grouped by their dir, and dlls not imported by any binary in the tree like plugins.
+    /// Dlls required by them are deployed next to them, except that plugins are loaded by the
+    /// exes of the main dir, so their dlls are deployed into the main dir, where dlls already
+    /// there are reused.
+    pub fn target_groups(&self) -> Vec<TargetGroup> {
+        let imported = self.imported_names();
+        let main_dir = self.main_dir();
+        let plugins = self.dlls.iter().filter(|dll| {
+            let name = dll.file_name().unwrap_or_default().to_string_lossy();
+            return !imported.contains(&name.to_lowercase());
+        });
+        let mut groups: Vec<TargetGroup> = Vec::new();
+        for (root, is_plugin) in self
+            .executables
+            .iter()
+            .map(|exe| (exe, false))
+            .chain(plugins.map(|dll| (dll, true)))
+        {
+            let dir = match (is_plugin, &main_dir) {
+                (true, Some(main_dir)) => main_dir.clone(),
+                _ => root.parent().unwrap_or(Path::new("")).to_path_buf(),
+            };
+            match groups.iter_mut().find(|g| g.output_dir == dir) {
+                Some(group) => group.targets.push(root.clone()),
+                None => groups.push(TargetGroup {
+                    output_dir: dir,
+                    targets: vec![root.clone()],
+                }),
+            }
+        }
+        return groups;
+    }
+
+    /// The dir of the exes nearest to the prefix, like `bin`. `None` if there is no exe.
+    fn main_dir(&self) -> Option<PathBuf> {
+        return self
+            .executables
+            .iter()
+            .filter_map(|exe| exe.parent())
+            .min_by_key(|dir| dir.components().count())
+            .map(Path::to_path_buf);
+    }
+
+    /// Lower case names of the dlls imported by binaries in the tree. Binaries whose imports
+    /// can't be read are skipped, they are reported when they are deployed for.
+    fn imported_names(&self) -> HashSet<String> {
+        return self
+            .executables
+            .iter()
+            .chain(&self.dlls)
+            .filter_map(|binary| get_dependencies(binary, false).ok())
+            .flatten()
+            .map(|dep| dep.dll_name.to_lowercase())
+            .collect();
+    }
+
+    /// Dirs that have dlls in the tree, so that those dlls are reused instead of searched.
+    pub fn dll_dirs(&self) -> Vec<PathBuf> {
+        let mut dirs: Vec<PathBuf> = Vec::new();
+        for dll in &self.dlls {
+            if let Some(dir) = dll.parent() {
+                if !dirs.iter().any(|d| d == dir) {
+                    dirs.push(dir.to_path_buf());
+                }
+            }
+        }
+        return dirs;
+    }
+}
+
+fn compile_globs(globs: &[String]) -> Result<Vec<Pattern>, Error> {
+    return globs
+        .iter()
+        .map(|g| {
+            return Pattern::new(g)
+                .map_err(|e| Error::config(format!("Invalid glob pattern \"{g}\": {e}")));
+        })
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_pe::TestPe;
+    use crate::util::test_dir;
+    use crate::Deployer;
+
+    #[test]
+    fn groups_plugins_into_main_dir() {
+        let prefix = test_dir("scan-install-tree");
+        let bin = prefix.join("bin");
+        let plugin_dir = bin.join("plugins").join("imageformats");
+        TestPe::exe()
+            .import("core.dll", &[])
+            .write(&bin.join("app.exe"));
+        TestPe::dll().write(&bin.join("core.dll"));
+        TestPe::dll()
+            .import("core.dll", &[])
+            .import("jpeg.dll", &[])
+            .write(&plugin_dir.join("qjpeg.dll"));
+        TestPe::dll().write(&plugin_dir.join("jpeg.dll"));
+        TestPe::exe()
+            .import("core.dll", &[])
+            .write(&prefix.join("tools").join("tool.exe"));
+        std::fs::write(bin.join("notes.dll"), "not a PE file").unwrap();
+        std::fs::write(prefix.join("tools").join("setup.exe"), "").unwrap();
+
+        let tree = InstallTree::scan(&prefix, &[], &[]).unwrap();
+        assert_eq!(
+            tree.skipped,
+            [
+                bin.join("notes.dll"),
+                prefix.join("tools").join("setup.exe")
+            ]
+        );
+        let groups = tree.target_groups();
+        let groups: Vec<(&Path, &[PathBuf])> = groups
+            .iter()
+            .map(|g| (g.output_dir.as_path(), g.targets.as_slice()))
+            .collect();
+        assert_eq!(
+            groups,
+            [
+                (
+                    bin.as_path(),
+                    [bin.join("app.exe"), plugin_dir.join("qjpeg.dll")].as_slice()
+                ),
+                (
+                    prefix.join("tools").as_path(),
+                    [prefix.join("tools").join("tool.exe")].as_slice()
+                ),
+            ]
+        );
+
+        // Only the dll of the plugin that is not in the main dir yet is deployed
+        let mut deployer = Deployer::new();
+        for dir in tree.dll_dirs() {
+            deployer = deployer.shallow_search_dir(dir);
+        }
+        let plans = deployer.plan_groups(&tree.target_groups()).unwrap();
+        let planned: Vec<Vec<(&str, &Path)>> = plans
+            .iter()
+            .map(|plan| {
+                return plan
+                    .dlls
+                    .iter()
+                    .map(|d| (d.name.as_str(), d.destination.as_path()))
+                    .collect();
+            })
+            .collect();
+        let tools_core = prefix.join("tools").join("core.dll");
+        assert_eq!(
+            planned,
+            [
+                vec![("jpeg.dll", bin.join("jpeg.dll").as_path())],
+                vec![("core.dll", tools_core.as_path())],
+            ]
+        );
+    }
+
+    #[test]
+    fn filters_by_globs() {
+        let prefix = test_dir("scan-globs");
+        TestPe::exe().write(&prefix.join("bin").join("app.exe"));
+        TestPe::exe().write(&prefix.join("tests").join("test.exe"));
+        TestPe::dll().write(&prefix.join("bin").join("a.dll"));
+
+        let include = ["BIN/**".to_string()];
+        let tree = InstallTree::scan(&prefix, &include, &[]).unwrap();
+        assert_eq!(tree.executables, [prefix.join("bin").join("app.exe")]);
+        assert_eq!(tree.dlls, [prefix.join("bin").join("a.dll")]);
+
+        let exclude = ["**/*.dll".to_string(), "tests/**".to_string()];
+        let tree = InstallTree::scan(&prefix, &[], &exclude).unwrap();
+        assert_eq!(tree.executables, [prefix.join("bin").join("app.exe")]);
+        assert!(tree.dlls.is_empty());
+
+        assert!(InstallTree::scan(&prefix.join("missing"), &[], &[]).is_err());
+        assert!(InstallTree::scan(&prefix, &["[".to_string()], &[]).is_err());
+    }
+}
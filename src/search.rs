@@ -0,0 +1,133 @@
+use crate::resolver::DllValidator;
+use crate::util::{
+    can_be_dir, is_file, normalize_separators, split_os_str, walk_dir, wine_to_host_path,
+};
+use std::path::{Path, PathBuf};
+
+pub(crate) fn existing_var_path(dest: &mut Vec<PathBuf>) {
+    if let Some(path) = std::env::var_os("PATH") {
+        for path in std::env::split_paths(&path) {
+            if !can_be_dir(&path) {
+                continue;
+            }
+            dest.push(path);
+        }
+    }
+}
+
+/// Dirs in a PATH-like environment variable. `WINEPATH` is always separated by `;`, other
+/// variables by the separator of the host, or by `;` if the value contains `;`. Windows paths are
+/// translated into host paths on other hosts.
+pub(crate) fn existing_search_var(name: &str, dest: &mut Vec<PathBuf>) {
+    let value = match std::env::var_os(name) {
+        Some(v) => v,
+        None => return,
+    };
+    let windows_style = name == "WINEPATH" || value.as_encoded_bytes().contains(&b';');
+    let entries: Vec<PathBuf> = if windows_style {
+        split_os_str(&value, b';')
+            .into_iter()
+            .map(PathBuf::from)
+            .collect()
+    } else {
+        std::env::split_paths(&value).collect()
+    };
+    for entry in entries {
+        if entry.as_os_str().is_empty() {
+            continue;
+        }
+        let entry = match wine_to_host_path(entry.as_os_str()) {
+            Some(path) => path,
+            None => normalize_separators(entry),
+        };
+        if can_be_dir(&entry) && !dest.contains(&entry) {
+            dest.push(entry);
+        }
+    }
+}
+
+pub(crate) fn validate_dll(dll_loc: &Path, verbose: bool, custom_validator: DllValidator) -> bool {
+    if !is_file(&dll_loc) {
+        return false;
+    }
+    if let Some(validate) = &custom_validator {
+        if let Err(reason) = validate(dll_loc) {
+            if verbose {
+                println!("Skipped \"{}\" because {reason}", dll_loc.display());
+            }
+            return false;
+        }
+    }
+    return true;
+}
+
+pub(crate) fn search_dll_deep(
+    name: &str,
+    dirs: &[PathBuf],
+    verbose: bool,
+    validate: DllValidator,
+) -> Option<PathBuf> {
+    for dir in dirs {
+        for entry in walk_dir(dir, |w| w) {
+            let (path, _) = match entry {
+                Ok(e) => e,
+                Err(e) => {
+                    if verbose {
+                        println!("Failed to search in \"{:?}\" because {}", e.path(), e);
+                    }
+                    continue;
+                }
+            };
+            let mut loc = path;
+            loc.push(name);
+
+            if !validate_dll(&loc, verbose, validate) {
+                continue;
+            }
+
+            return Some(loc);
+        }
+    }
+
+    return None;
+}
+
+pub(crate) fn search_dll_shallow(
+    name: &str,
+    dirs: &[PathBuf],
+    verbose: bool,
+    validate: DllValidator,
+) -> Option<PathBuf> {
+    for path in dirs {
+        let mut loc = path.clone();
+        loc.push(name);
+
+        if !validate_dll(&loc, verbose, validate) {
+            continue;
+        }
+
+        return Some(loc);
+    }
+    return None;
+}
+
+/// All distinct files named `name` in shallow search dirs.
+pub(crate) fn shallow_candidates(
+    name: &str,
+    dirs: &[PathBuf],
+    verbose: bool,
+    validate: DllValidator,
+) -> Vec<PathBuf> {
+    let mut candidates: Vec<PathBuf> = Vec::new();
+    for path in dirs {
+        let mut loc = path.clone();
+        loc.push(name);
+        if !validate_dll(&loc, verbose, validate) {
+            continue;
+        }
+        if !candidates.contains(&loc) {
+            candidates.push(loc);
+        }
+    }
+    return candidates;
+}
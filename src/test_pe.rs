@@ -0,0 +1,397 @@
+//! Minimal PE32+ files written by tests, with only the parts the deployer reads: the machine
+//! type, the linker version, imports, exports, the file version and the certificate table.
+
+use crate::validator::FileVersion;
+use std::path::Path;
+
+const FILE_ALIGNMENT: usize = 0x200;
+const SECTION_ALIGNMENT: usize = 0x1000;
+/// RVA of the only section, which is at file offset `FILE_ALIGNMENT`
+const SECTION_RVA: usize = SECTION_ALIGNMENT;
+
+/// Builds a PE32+ exe or dll.
+#[derive(Debug, Clone)]
+pub(crate) struct TestPe {
+    dll: bool,
+    machine: u16,
+    linker_version: (u8, u8),
+    timestamp: u32,
+    imports: Vec<(String, Vec<String>)>,
+    exports: Vec<String>,
+    file_version: Option<FileVersion>,
+    signed: bool,
+}
+
+impl TestPe {
+    pub(crate) fn dll() -> TestPe {
+        return TestPe {
+            dll: true,
+            machine: 0x8664,
+            linker_version: (14, 0),
+            timestamp: 0,
+            imports: Vec::new(),
+            exports: Vec::new(),
+            file_version: None,
+            signed: false,
+        };
+    }
+
+    pub(crate) fn exe() -> TestPe {
+        return TestPe {
+            dll: false,
+            ..TestPe::dll()
+        };
+    }
+
+    pub(crate) fn machine(mut self, machine: u16) -> Self {
+        self.machine = machine;
+        return self;
+    }
+
+    pub(crate) fn linker_version(mut self, major: u8, minor: u8) -> Self {
+        self.linker_version = (major, minor);
+        return self;
+    }
+
+    /// Import symbols from `dll`, `#<n>` imports by ordinal
+    pub(crate) fn import(mut self, dll: &str, symbols: &[&str]) -> Self {
+        let symbols = symbols.iter().map(|s| s.to_string()).collect();
+        self.imports.push((dll.to_string(), symbols));
+        return self;
+    }
+
+    pub(crate) fn export(mut self, symbol: &str) -> Self {
+        self.exports.push(symbol.to_string());
+        return self;
+    }
+
+    /// Files that differ only in the timestamp have the same size but different content
+    pub(crate) fn timestamp(mut self, timestamp: u32) -> Self {
+        self.timestamp = timestamp;
+        return self;
+    }
+
+    pub(crate) fn file_version(mut self, version: &str) -> Self {
+        self.file_version = Some(version.parse().unwrap());
+        return self;
+    }
+
+    /// Embed a certificate table holding an empty PKCS#7 SignedData blob
+    pub(crate) fn signed(mut self) -> Self {
+        self.signed = true;
+        return self;
+    }
+
+    pub(crate) fn write(&self, path: &Path) {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        std::fs::write(path, self.bytes()).unwrap();
+    }
+
+    pub(crate) fn bytes(&self) -> Vec<u8> {
+        let mut section = Buffer::default();
+        let mut directories = [(0u32, 0u32); 16];
+        if !self.imports.is_empty() {
+            directories[1] = self.write_imports(&mut section);
+        }
+        if !self.exports.is_empty() {
+            directories[0] = self.write_exports(&mut section);
+        }
+        if let Some(version) = self.file_version {
+            directories[2] = write_version_resource(&mut section, version);
+        }
+        section.align(16);
+        let raw_size = align(section.0.len(), FILE_ALIGNMENT);
+        let image_size = SECTION_RVA + align(section.0.len(), SECTION_ALIGNMENT);
+
+        let mut file = Buffer::default();
+        // DOS header, e_lfanew points right after it
+        file.bytes(b"MZ");
+        file.zeros(0x3A);
+        file.u32(0x40);
+        file.bytes(b"PE\0\0");
+        file.u16(self.machine);
+        file.u16(1);
+        file.u32(self.timestamp);
+        file.u32(0);
+        file.u32(0);
+        file.u16(240);
+        file.u16(if self.dll { 0x2022 } else { 0x0022 });
+        // Optional header
+        file.u16(0x20B);
+        file.0.push(self.linker_version.0);
+        file.0.push(self.linker_version.1);
+        file.u32(0);
+        file.u32(raw_size as u32);
+        file.u32(0);
+        file.u32(0);
+        file.u32(SECTION_RVA as u32);
+        file.u64(0x1_8000_0000);
+        file.u32(SECTION_ALIGNMENT as u32);
+        file.u32(FILE_ALIGNMENT as u32);
+        for version in [6, 0, 0, 0, 6, 0] {
+            file.u16(version);
+        }
+        file.u32(0);
+        file.u32(image_size as u32);
+        file.u32(FILE_ALIGNMENT as u32);
+        file.u32(0);
+        file.u16(if self.dll { 2 } else { 3 });
+        file.u16(0x160);
+        for size in [0x100000, 0x1000, 0x100000, 0x1000] {
+            file.u64(size);
+        }
+        file.u32(0);
+        file.u32(16);
+        let directories_at = file.0.len();
+        file.zeros(16 * 8);
+        // Section header
+        file.bytes(b".rdata\0\0");
+        file.u32(section.0.len() as u32);
+        file.u32(SECTION_RVA as u32);
+        file.u32(raw_size as u32);
+        file.u32(FILE_ALIGNMENT as u32);
+        file.zeros(12);
+        file.u32(0x4000_0040);
+        file.0.resize(FILE_ALIGNMENT, 0);
+        file.bytes(&section.0);
+        file.0.resize(FILE_ALIGNMENT + raw_size, 0);
+
+        if self.signed {
+            // The certificate table is addressed by file offset
+            let offset = file.0.len();
+            let blob = signed_data_blob();
+            let length = 8 + blob.len();
+            file.u32(length as u32);
+            file.u16(0x0200);
+            file.u16(2);
+            file.bytes(&blob);
+            file.align(8);
+            directories[4] = (offset as u32, (file.0.len() - offset) as u32);
+        }
+        for (index, (address, size)) in directories.iter().enumerate() {
+            file.patch_u32(directories_at + index * 8, *address);
+            file.patch_u32(directories_at + index * 8 + 4, *size);
+        }
+        return file.0;
+    }
+
+    /// Import descriptors, lookup and address tables, hint/name entries and dll names.
+    fn write_imports(&self, section: &mut Buffer) -> (u32, u32) {
+        section.align(8);
+        let descriptors = section.0.len();
+        let size = (self.imports.len() + 1) * 20;
+        section.zeros(size);
+        for (index, (dll, symbols)) in self.imports.iter().enumerate() {
+            let mut names = Vec::new();
+            for symbol in symbols {
+                if symbol.starts_with('#') {
+                    names.push(None);
+                    continue;
+                }
+                section.align(2);
+                names.push(Some(rva(section.0.len())));
+                section.u16(0);
+                section.bytes(symbol.as_bytes());
+                section.0.push(0);
+            }
+            let dll_name = rva(section.0.len());
+            section.bytes(dll.as_bytes());
+            section.0.push(0);
+
+            let mut tables = [0u32; 2];
+            for table in &mut tables {
+                section.align(8);
+                *table = rva(section.0.len());
+                for (symbol, name) in symbols.iter().zip(&names) {
+                    match (name, symbol.strip_prefix('#')) {
+                        (Some(name), _) => section.u64(*name as u64),
+                        (None, Some(ordinal)) => {
+                            section.u64(0x8000_0000_0000_0000 | ordinal.parse::<u64>().unwrap())
+                        }
+                        (None, None) => unreachable!(),
+                    }
+                }
+                section.u64(0);
+            }
+            let descriptor = descriptors + index * 20;
+            section.patch_u32(descriptor, tables[0]);
+            section.patch_u32(descriptor + 12, dll_name);
+            section.patch_u32(descriptor + 16, tables[1]);
+        }
+        return (rva(descriptors), size as u32);
+    }
+
+    /// An export directory with sorted names, every function points to the section start.
+    fn write_exports(&self, section: &mut Buffer) -> (u32, u32) {
+        let mut names = self.exports.clone();
+        names.sort();
+        section.align(4);
+        let directory = section.0.len();
+        section.zeros(40);
+        let functions = rva(section.0.len());
+        for _ in &names {
+            section.u32(SECTION_RVA as u32);
+        }
+        let name_pointers = section.0.len();
+        section.zeros(names.len() * 4);
+        let ordinals = rva(section.0.len());
+        for index in 0..names.len() {
+            section.u16(index as u16);
+        }
+        for (index, name) in names.iter().enumerate() {
+            section.patch_u32(name_pointers + index * 4, rva(section.0.len()));
+            section.bytes(name.as_bytes());
+            section.0.push(0);
+        }
+        let dll_name = rva(section.0.len());
+        section.bytes(b"test.dll\0");
+        let count = names.len() as u32;
+        section.patch_u32(directory + 12, dll_name);
+        section.patch_u32(directory + 16, 1);
+        section.patch_u32(directory + 20, count);
+        section.patch_u32(directory + 24, count);
+        section.patch_u32(directory + 28, functions);
+        section.patch_u32(directory + 32, rva(name_pointers));
+        section.patch_u32(directory + 36, ordinals);
+        return (rva(directory), (section.0.len() - directory) as u32);
+    }
+}
+
+/// A resource tree of `RT_VERSION/1/0x409` holding a `VS_VERSIONINFO` with only the fixed info.
+fn write_version_resource(section: &mut Buffer, version: FileVersion) -> (u32, u32) {
+    const RT_VERSION: u32 = 16;
+    section.align(4);
+    let root = section.0.len();
+    // Offsets in the tree are relative to its root, the high bit marks a subdirectory
+    for (id, next) in [(RT_VERSION, 0x18), (1, 0x30), (0x409, 0x48)] {
+        section.zeros(14);
+        section.u16(1);
+        section.u32(id);
+        section.u32(if next == 0x48 {
+            next
+        } else {
+            0x8000_0000 | next
+        });
+    }
+    let data_entry = section.0.len();
+    section.zeros(16);
+
+    section.align(4);
+    let info = section.0.len();
+    let [major, minor, patch, build] = version.0;
+    section.u16(92);
+    section.u16(52);
+    section.u16(0);
+    for unit in "VS_VERSION_INFO\0".encode_utf16() {
+        section.u16(unit);
+    }
+    section.align(4);
+    section.u32(0xFEEF_04BD);
+    section.u32(0x0001_0000);
+    for _ in 0..2 {
+        section.u32(((major as u32) << 16) | minor as u32);
+        section.u32(((patch as u32) << 16) | build as u32);
+    }
+    section.zeros(28);
+    section.patch_u32(data_entry, rva(info));
+    section.patch_u32(data_entry + 4, (section.0.len() - info) as u32);
+    return (rva(root), (section.0.len() - root) as u32);
+}
+
+/// DER encoded ContentInfo of type signedData with empty content
+fn signed_data_blob() -> Vec<u8> {
+    return vec![
+        0x30, 0x0F, 0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x07, 0x02, 0xA0, 0x02,
+        0x30, 0x00,
+    ];
+}
+
+fn align(value: usize, alignment: usize) -> usize {
+    return value.div_ceil(alignment) * alignment;
+}
+
+fn rva(offset: usize) -> u32 {
+    return (SECTION_RVA + offset) as u32;
+}
+
+#[derive(Debug, Default)]
+struct Buffer(Vec<u8>);
+
+impl Buffer {
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes);
+    }
+
+    fn zeros(&mut self, count: usize) {
+        self.0.resize(self.0.len() + count, 0);
+    }
+
+    fn align(&mut self, alignment: usize) {
+        self.0.resize(align(self.0.len(), alignment), 0);
+    }
+
+    fn u16(&mut self, value: u16) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    fn u64(&mut self, value: u64) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    fn patch_u32(&mut self, at: usize, value: u32) {
+        self.0[at..at + 4].copy_from_slice(&value.to_le_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pe::{get_dependencies, get_linker_version, get_machine, ImportedSymbol};
+    use crate::util::test_dir;
+    use crate::validator::file_version;
+    use pelite::PeFile;
+
+    #[test]
+    fn writes_parsable_pe() {
+        let dir = test_dir("test-pe");
+        let path = dir.join("a.exe");
+        TestPe::exe()
+            .machine(0x14c)
+            .linker_version(14, 38)
+            .import("B.dll", &["foo", "#7"])
+            .import("c.dll", &[])
+            .write(&path);
+        assert_eq!(get_machine(&path), Some(0x14c));
+        assert_eq!(get_linker_version(&path), Some((14, 38)));
+        let deps = get_dependencies(&path, false).unwrap();
+        let names: Vec<&str> = deps.iter().map(|d| d.dll_name.as_str()).collect();
+        assert_eq!(names, ["b.dll", "c.dll"]);
+        let foo = ImportedSymbol::Name("foo".to_string());
+        assert_eq!(deps[0].symbols, [foo, ImportedSymbol::Ordinal(7)]);
+    }
+
+    #[test]
+    fn writes_exports_version_and_certificate() {
+        let bytes = TestPe::dll()
+            .export("foo")
+            .export("bar")
+            .file_version("14.38.33130.1")
+            .signed()
+            .bytes();
+        let image = PeFile::from_bytes(&bytes).unwrap();
+        let by = image.exports().unwrap().by().unwrap();
+        assert!(by.name("foo").is_ok() && by.name("bar").is_ok());
+        assert!(by.name("baz").is_err());
+        let version = file_version(&image).unwrap();
+        assert_eq!(version.to_string(), "14.38.33130.1");
+        let security = image.security().unwrap();
+        assert_eq!(security.image().wCertificateType, 2);
+        assert!(TestPe::dll().bytes().len() < bytes.len());
+    }
+}
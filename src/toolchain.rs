@@ -0,0 +1,203 @@
+use crate::util::{can_be_dir, long_path, normalize_separators};
+use crate::validator::FileVersion;
+use std::path::{Path, PathBuf};
+
+/// Threads flavors of MinGW GCC on Debian and Ubuntu, installed side by side like
+/// `/usr/lib/gcc/x86_64-w64-mingw32/12-posix`
+const THREAD_FLAVORS: [&str; 2] = ["posix", "win32"];
+
+/// Prefixes searched for a toolchain given only by its target triple
+const DEFAULT_PREFIXES: [&str; 2] = ["/usr", "/usr/local"];
+
+/// Target triple in the name of a cross compiler, like `x86_64-w64-mingw32` in
+/// `x86_64-w64-mingw32-g++-posix` or `aarch64-w64-windows-gnu` in
+/// `aarch64-w64-windows-gnu-clang++`.
+pub fn triple_from_compiler(compiler: &Path) -> Option<String> {
+    let name = compiler.file_name()?.to_string_lossy().to_lowercase();
+    let parts: Vec<&str> = name.split('-').collect();
+    if parts.len() < 4 || parts[1] != "w64" {
+        return None;
+    }
+    let length = if parts[2] == "windows" && parts.get(3) == Some(&"gnu") && parts.len() > 4 {
+        4
+    } else {
+        3
+    };
+    return Some(parts[..length].join("-"));
+}
+
+/// Dirs of the runtime dlls of a MinGW toolchain, like `libstdc++-6.dll`, `libgcc_s_seh-1.dll`,
+/// `libwinpthread-1.dll`, `libc++.dll` and `libunwind.dll`, in the order they should be searched.
+///
+/// The compiler dir comes first, which is where MSYS2 and llvm-mingw on Windows keep them. Then
+/// `<prefix>/<triple>/bin`, `<prefix>/<triple>/sys-root/mingw/bin`, the newest
+/// `<prefix>/lib/gcc/<triple>/<version>` and `<prefix>/<triple>/lib`, where `<prefix>` is the
+/// parent of the compiler dir. Without a compiler, `MINGW_PREFIX` set by MSYS2, `/usr` and
+/// `/usr/local` are used as prefixes. The triple is taken from the name of the compiler if not
+/// given.
+pub fn toolchain_runtime_dirs(triple: Option<&str>, compiler: Option<&Path>) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    let mut prefixes: Vec<PathBuf> = Vec::new();
+    let mut thread_flavor = None;
+    let mut triple = triple.map(str::to_string);
+
+    if let Some(compiler) = compiler {
+        let compiler = normalize_separators(compiler);
+        let compiler = std::path::absolute(&compiler).unwrap_or(compiler);
+        let mut paths = vec![compiler.clone()];
+        // Follow symlinks like /usr/bin/x86_64-w64-mingw32-g++ -> x86_64-w64-mingw32-g++-posix
+        if std::fs::symlink_metadata(&compiler).is_ok_and(|m| m.is_symlink()) {
+            paths.extend(std::fs::canonicalize(&compiler).ok());
+        }
+        for path in &paths {
+            if triple.is_none() {
+                triple = triple_from_compiler(path);
+            }
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            if let Some(flavor) = THREAD_FLAVORS.iter().find(|f| name.ends_with(*f)) {
+                thread_flavor = Some(*flavor);
+            }
+            if let Some(dir) = path.parent() {
+                dirs.push(dir.to_path_buf());
+                prefixes.extend(dir.parent().map(Path::to_path_buf));
+            }
+        }
+    } else {
+        if let Some(prefix) = std::env::var_os("MINGW_PREFIX") {
+            let prefix = normalize_separators(prefix);
+            dirs.push(prefix.join("bin"));
+            prefixes.push(prefix);
+        }
+        prefixes.extend(DEFAULT_PREFIXES.iter().map(PathBuf::from));
+    }
+
+    if let Some(triple) = &triple {
+        for prefix in &prefixes {
+            dirs.push(prefix.join(triple).join("bin"));
+            dirs.push(prefix.join(triple).join("sys-root/mingw/bin"));
+            dirs.extend(gcc_version_dirs(
+                &prefix.join("lib/gcc").join(triple),
+                thread_flavor,
+            ));
+            dirs.push(prefix.join(triple).join("lib"));
+        }
+    }
+
+    let mut unique: Vec<PathBuf> = Vec::new();
+    for dir in dirs {
+        if can_be_dir(&dir) && !unique.contains(&dir) {
+            unique.push(dir);
+        }
+    }
+    return unique;
+}
+
+/// Version dirs of GCC like `12`, `12.2.0` or `12-posix`, newest first. Dirs of `thread_flavor`
+/// come before other dirs of the same version.
+fn gcc_version_dirs(dir: &Path, thread_flavor: Option<&str>) -> Vec<PathBuf> {
+    let entries = match std::fs::read_dir(long_path(dir)) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+    let mut versions: Vec<(FileVersion, bool, PathBuf)> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| dir.join(e.file_name()))
+        .filter(can_be_dir)
+        .filter_map(|d| {
+            let name = d.file_name()?.to_str()?.to_string();
+            let (version, flavor) = match name.split_once('-') {
+                Some((version, flavor)) => (version, Some(flavor)),
+                None => (name.as_str(), None),
+            };
+            let version: FileVersion = version.parse().ok()?;
+            let preferred = flavor.is_none() || flavor == thread_flavor;
+            return Some((version, preferred, d));
+        })
+        .collect();
+    versions.sort_by(|a, b| b.0.cmp(&a.0).then(b.1.cmp(&a.1)).then(a.2.cmp(&b.2)));
+    return versions.into_iter().map(|(_, _, d)| d).collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::test_dir;
+
+    #[test]
+    fn takes_triple_from_compiler_name() {
+        let triple = |name: &str| triple_from_compiler(Path::new(name));
+        assert_eq!(
+            triple("/usr/bin/x86_64-w64-mingw32-g++-posix").as_deref(),
+            Some("x86_64-w64-mingw32")
+        );
+        assert_eq!(
+            triple("i686-w64-mingw32-gcc").as_deref(),
+            Some("i686-w64-mingw32")
+        );
+        assert_eq!(
+            triple("aarch64-w64-windows-gnu-clang++").as_deref(),
+            Some("aarch64-w64-windows-gnu")
+        );
+        assert_eq!(
+            triple("X86_64-W64-MINGW32-GCC.EXE").as_deref(),
+            Some("x86_64-w64-mingw32")
+        );
+        assert_eq!(triple("/usr/bin/g++"), None);
+        assert_eq!(triple("x86_64-w64-mingw32"), None);
+        assert_eq!(triple("x86_64-pc-linux-gnu-gcc"), None);
+    }
+
+    #[test]
+    fn sorts_gcc_version_dirs() {
+        let dir = test_dir("gcc-versions");
+        for name in ["9", "10-win32", "10-posix", "12.2.0", "12", "include-fixed"] {
+            std::fs::create_dir_all(dir.join(name)).unwrap();
+        }
+        std::fs::write(dir.join("13"), b"").unwrap();
+
+        let names = |flavor| {
+            return gcc_version_dirs(&dir, flavor)
+                .iter()
+                .map(|d| d.file_name().unwrap().to_string_lossy().to_string())
+                .collect::<Vec<String>>();
+        };
+        assert_eq!(
+            names(Some("win32")),
+            ["12.2.0", "12", "10-win32", "10-posix", "9"]
+        );
+        assert_eq!(
+            names(Some("posix")),
+            ["12.2.0", "12", "10-posix", "10-win32", "9"]
+        );
+        assert_eq!(names(None), ["12.2.0", "12", "10-posix", "10-win32", "9"]);
+        assert!(gcc_version_dirs(&dir.join("missing"), None).is_empty());
+    }
+
+    #[test]
+    fn finds_runtime_dirs_of_compiler() {
+        let prefix = test_dir("toolchain").join("usr");
+        let triple = "x86_64-w64-mingw32";
+        for dir in [
+            "bin",
+            "x86_64-w64-mingw32/bin",
+            "x86_64-w64-mingw32/lib",
+            "lib/gcc/x86_64-w64-mingw32/12-posix",
+            "lib/gcc/x86_64-w64-mingw32/12-win32",
+        ] {
+            std::fs::create_dir_all(prefix.join(dir)).unwrap();
+        }
+        let compiler = prefix.join("bin/x86_64-w64-mingw32-g++-win32");
+        std::fs::write(&compiler, b"").unwrap();
+
+        assert_eq!(
+            toolchain_runtime_dirs(None, Some(&compiler)),
+            [
+                prefix.join("bin"),
+                prefix.join(triple).join("bin"),
+                prefix.join("lib/gcc").join(triple).join("12-win32"),
+                prefix.join("lib/gcc").join(triple).join("12-posix"),
+                prefix.join(triple).join("lib"),
+            ]
+        );
+    }
+}
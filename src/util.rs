@@ -0,0 +1,437 @@
+use crate::error::Error;
+use std::borrow::Cow;
+use std::ffi::{OsStr, OsString};
+use std::path::{Component, Path, PathBuf, Prefix};
+use walkdir::WalkDir;
+
+pub fn can_be_dir<P: AsRef<Path>>(path: &P) -> bool {
+    if let Ok(md) = std::fs::metadata(long_path(path.as_ref())) {
+        if md.is_dir() {
+            return true;
+        }
+    }
+    return false;
+}
+
+pub fn is_file<P: AsRef<Path>>(path: &P) -> bool {
+    if let Ok(md) = std::fs::metadata(long_path(path.as_ref())) {
+        return md.is_file();
+    }
+    return false;
+}
+
+/// Size of the file in bytes, 0 if it can't be read.
+pub fn file_size<P: AsRef<Path>>(path: &P) -> u64 {
+    return std::fs::metadata(long_path(path.as_ref()))
+        .map(|md| md.len())
+        .unwrap_or(0);
+}
+
+pub fn display_filename(path: &Path) -> String {
+    return match path.file_name() {
+        Some(name) => name.to_string_lossy().to_string(),
+        None => path.display().to_string(),
+    };
+}
+
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64;
+    let mut unit = "B";
+    for u in UNITS {
+        if size < 1024.0 {
+            break;
+        }
+        size /= 1024.0;
+        unit = u;
+    }
+    return format!("{size:.2} {unit}");
+}
+
+/// Parse sizes like `1024`, `300K`, `200M` or `1.5GiB`. Units are powers of 1024.
+pub fn parse_size(s: &str) -> Result<u64, String> {
+    let s = s.trim();
+    let (number, unit) = match s.find(|c: char| c.is_ascii_alphabetic()) {
+        Some(idx) => s.split_at(idx),
+        None => (s, ""),
+    };
+    let scale: u64 = match unit.to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" | "KIB" => 1 << 10,
+        "M" | "MB" | "MIB" => 1 << 20,
+        "G" | "GB" | "GIB" => 1 << 30,
+        _ => return Err(format!("Unknown size unit \"{unit}\"")),
+    };
+    let number: f64 = match number.trim().parse() {
+        Ok(n) if n >= 0.0 => n,
+        _ => return Err(format!("Invalid size \"{s}\"")),
+    };
+    return Ok((number * scale as f64) as u64);
+}
+
+/// Split `s` on an ASCII separator like `;`, without converting it to UTF-8.
+pub fn split_os_str(s: &OsStr, separator: u8) -> Vec<OsString> {
+    debug_assert!(separator.is_ascii());
+    return s
+        .as_encoded_bytes()
+        .split(|b| *b == separator)
+        .map(|part| {
+            // Safety: `part` is split from encoded bytes at an ASCII char
+            return unsafe { OsStr::from_encoded_bytes_unchecked(part) }.to_os_string();
+        })
+        .collect();
+}
+
+/// Replace every `@file` argument after the program name with the arguments in the response
+/// file. `@@arg` is passed as `@arg` without reading a file.
+///
+/// A response file has one argument per line. Empty lines and lines starting with `#` are
+/// skipped, and spaces around arguments are trimmed. An argument wrapped in `"` is taken as is,
+/// except that `\"` and `\\` in it are unescaped. Response files are not expanded recursively.
+pub fn expand_response_files<I>(args: I) -> Result<Vec<OsString>, Error>
+where
+    I: IntoIterator<Item = OsString>,
+{
+    // The program name is kept even if it starts with `@`
+    let mut args = args.into_iter();
+    let mut expanded: Vec<OsString> = args.next().into_iter().collect();
+    for arg in args {
+        let file = match arg.as_encoded_bytes().strip_prefix(b"@") {
+            // Safety: both are split after an ASCII char
+            Some(escaped) if escaped.starts_with(b"@") => {
+                expanded.push(unsafe { OsStr::from_encoded_bytes_unchecked(escaped) }.into());
+                continue;
+            }
+            Some(file) => PathBuf::from(unsafe { OsStr::from_encoded_bytes_unchecked(file) }),
+            None => {
+                expanded.push(arg);
+                continue;
+            }
+        };
+        let content = std::fs::read(long_path(&file)).map_err(|e| {
+            return Error::io(
+                format!("Failed to read response file \"{}\"", file.display()),
+                e,
+            );
+        })?;
+        let parse_error = |line: usize, message: &str| Error::Parse {
+            path: file.clone(),
+            message: format!("line {line}: {message}"),
+        };
+        let content = content.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(&content);
+        for (index, line) in content.split(|b| *b == b'\n').enumerate() {
+            let line = line.trim_ascii();
+            if line.is_empty() || line[0] == b'#' {
+                continue;
+            }
+            let arg = if line[0] == b'"' {
+                if line.len() < 2 || line[line.len() - 1] != b'"' {
+                    return Err(parse_error(index + 1, "unterminated quote"));
+                }
+                unescape_quoted(&line[1..line.len() - 1])
+            } else {
+                line.to_vec()
+            };
+            let arg = bytes_to_os_string(arg)
+                .map_err(|_| parse_error(index + 1, "argument is not valid UTF-8"))?;
+            expanded.push(arg);
+        }
+    }
+    return Ok(expanded);
+}
+
+fn unescape_quoted(quoted: &[u8]) -> Vec<u8> {
+    let mut arg = Vec::with_capacity(quoted.len());
+    let mut iter = quoted.iter().peekable();
+    while let Some(b) = iter.next() {
+        if *b == b'\\' {
+            if let Some(&&next @ (b'"' | b'\\')) = iter.peek() {
+                arg.push(next);
+                iter.next();
+                continue;
+            }
+        }
+        arg.push(*b);
+    }
+    return arg;
+}
+
+#[cfg(unix)]
+fn bytes_to_os_string(bytes: Vec<u8>) -> Result<OsString, std::string::FromUtf8Error> {
+    use std::os::unix::ffi::OsStringExt;
+    return Ok(OsString::from_vec(bytes));
+}
+
+#[cfg(not(unix))]
+fn bytes_to_os_string(bytes: Vec<u8>) -> Result<OsString, std::string::FromUtf8Error> {
+    return String::from_utf8(bytes).map(OsString::from);
+}
+
+/// On non-Windows hosts, replace `\` with `/`, so that paths passed by Windows tools or CMake
+/// scripts work. Paths are not changed on Windows.
+pub fn normalize_separators<P: AsRef<Path>>(path: P) -> PathBuf {
+    let path = path.as_ref();
+    if cfg!(windows) {
+        return path.to_path_buf();
+    }
+    let bytes: Vec<u8> = path
+        .as_os_str()
+        .as_encoded_bytes()
+        .iter()
+        .map(|b| if *b == b'\\' { b'/' } else { *b })
+        .collect();
+    // Safety: an ASCII char is replaced by another ASCII char
+    let path = unsafe { OsString::from_encoded_bytes_unchecked(bytes) };
+    return PathBuf::from(path);
+}
+
+/// Translate a Windows path like `Z:\usr\x86_64-w64-mingw32\bin` into a host path, using the
+/// drive mapping of Wine. `Z:` is the root dir, other drives are looked up in
+/// `$WINEPREFIX/dosdevices`. Returns `None` on Windows or if `path` doesn't start with a drive.
+pub fn wine_to_host_path(path: &OsStr) -> Option<PathBuf> {
+    if cfg!(windows) {
+        return None;
+    }
+    let prefix = match std::env::var_os("WINEPREFIX") {
+        Some(prefix) => Some(PathBuf::from(prefix)),
+        None => std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".wine")),
+    };
+    return wine_to_host_path_in(path, prefix.as_deref());
+}
+
+/// Translate a Windows path like [`wine_to_host_path`] with the drives of the Wine prefix
+/// `prefix`. Only `Z:` is translated without a prefix.
+fn wine_to_host_path_in(path: &OsStr, prefix: Option<&Path>) -> Option<PathBuf> {
+    let bytes = path.as_encoded_bytes();
+    let is_drive = bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':';
+    if !is_drive || (bytes.len() > 2 && bytes[2] != b'\\' && bytes[2] != b'/') {
+        return None;
+    }
+    let drive = bytes[0].to_ascii_lowercase();
+    // Safety: split after ASCII chars
+    let rest = unsafe { OsStr::from_encoded_bytes_unchecked(&bytes[2..]) };
+    let rest = normalize_separators(rest);
+    let rest = rest.strip_prefix("/").unwrap_or(&rest);
+    if drive == b'z' {
+        return Some(Path::new("/").join(rest));
+    }
+    return Some(
+        prefix?
+            .join("dosdevices")
+            .join(format!("{}:", drive as char))
+            .join(rest),
+    );
+}
+
+/// On Windows, prefix absolute paths longer than `MAX_PATH` with `\\?\`, so that they can be
+/// opened without long path support enabled in the system. Other paths are returned as is.
+pub fn long_path(path: &Path) -> Cow<'_, Path> {
+    const MAX_PATH: usize = 260;
+    if path.as_os_str().len() < MAX_PATH {
+        return Cow::Borrowed(path);
+    }
+    return verbatim_path(path);
+}
+
+/// On Windows, prefix absolute paths with `\\?\`. Other paths are returned as is.
+fn verbatim_path(path: &Path) -> Cow<'_, Path> {
+    if !cfg!(windows) || !path.is_absolute() {
+        return Cow::Borrowed(path);
+    }
+
+    // Verbatim paths are not normalized by Windows, so `/`, `.` and `..` must be resolved here
+    let mut verbatim = OsString::new();
+    let mut parts: Vec<&OsStr> = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Prefix(prefix) => match prefix.kind() {
+                Prefix::Disk(letter) => {
+                    verbatim.push(format!(r"\\?\{}:", letter as char));
+                }
+                Prefix::UNC(server, share) => {
+                    verbatim.push(r"\\?\UNC\");
+                    verbatim.push(server);
+                    verbatim.push(r"\");
+                    verbatim.push(share);
+                }
+                _ => return Cow::Borrowed(path),
+            },
+            Component::RootDir | Component::CurDir => {}
+            Component::ParentDir => {
+                parts.pop();
+            }
+            Component::Normal(part) => parts.push(part),
+        }
+    }
+    for part in parts {
+        verbatim.push(r"\");
+        verbatim.push(part);
+    }
+    return Cow::Owned(PathBuf::from(verbatim));
+}
+
+/// Walk `dir` recursively, configured by `configure`. On Windows the walk goes through the
+/// `\\?\` form of `dir`, so that entries whose paths are longer than `MAX_PATH` are found too.
+/// Entries are returned with their paths under `dir`.
+pub fn walk_dir(
+    dir: &Path,
+    configure: impl FnOnce(WalkDir) -> WalkDir,
+) -> impl Iterator<Item = walkdir::Result<(PathBuf, walkdir::DirEntry)>> {
+    let root = verbatim_path(dir).into_owned();
+    let dir = dir.to_path_buf();
+    return configure(WalkDir::new(&root))
+        .into_iter()
+        .map(move |entry| {
+            let entry = entry?;
+            let path = match entry.path().strip_prefix(&root) {
+                Ok(relative) if relative.as_os_str().is_empty() => dir.clone(),
+                Ok(relative) => dir.join(relative),
+                Err(_) => entry.path().to_path_buf(),
+            };
+            return Ok((path, entry));
+        });
+}
+
+/// Sorted subdirs, empty if `dir` can't be read
+pub fn subdirs(dir: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = match std::fs::read_dir(long_path(dir)) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .map(|e| dir.join(e.file_name()))
+            .filter(can_be_dir)
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    dirs.sort();
+    return dirs;
+}
+
+/// A new empty dir under the temp dir for a test, removed first if it exists.
+#[cfg(test)]
+pub(crate) fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir()
+        .join("shared_lib_deployer-tests")
+        .join(format!("{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    return dir;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sizes_with_units() {
+        assert_eq!(parse_size("1024"), Ok(1024));
+        assert_eq!(parse_size("12B"), Ok(12));
+        assert_eq!(parse_size("300K"), Ok(300 << 10));
+        assert_eq!(parse_size("200mb"), Ok(200 << 20));
+        assert_eq!(parse_size("1.5GiB"), Ok(3 << 29));
+        assert_eq!(parse_size(" 2 MiB "), Ok(2 << 20));
+    }
+
+    #[test]
+    fn rejects_invalid_sizes() {
+        assert!(parse_size("10T").is_err());
+        assert!(parse_size("-1K").is_err());
+        assert!(parse_size("K").is_err());
+        assert!(parse_size("").is_err());
+    }
+
+    #[test]
+    fn formats_sizes() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1536), "1.50 KiB");
+        assert_eq!(format_size(200 << 20), "200.00 MiB");
+        assert_eq!(format_size(3 << 29), "1.50 GiB");
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn translates_wine_paths() {
+        let prefix = Path::new("/home/user/.wine-test");
+        let host = |p: &str| wine_to_host_path_in(OsStr::new(p), Some(prefix));
+        assert_eq!(host(r"Z:\usr\bin"), Some(PathBuf::from("/usr/bin")));
+        assert_eq!(host("z:/usr/bin"), Some(PathBuf::from("/usr/bin")));
+        assert_eq!(host("Z:"), Some(PathBuf::from("/")));
+        assert_eq!(
+            host(r"C:\windows\system32"),
+            Some(PathBuf::from(
+                "/home/user/.wine-test/dosdevices/c:/windows/system32"
+            ))
+        );
+        let no_prefix = |p: &str| wine_to_host_path_in(OsStr::new(p), None);
+        assert_eq!(no_prefix("Z:/usr/bin"), Some(PathBuf::from("/usr/bin")));
+        assert_eq!(no_prefix(r"C:\windows"), None);
+
+        assert_eq!(host(r"usr\bin"), None);
+        assert_eq!(host("/usr/bin"), None);
+        assert_eq!(host("C:windows"), None);
+    }
+
+    fn expand(args: &[&str]) -> Result<Vec<OsString>, Error> {
+        return expand_response_files(args.iter().map(OsString::from));
+    }
+
+    #[test]
+    fn expands_response_files() {
+        let dir = test_dir("response-files");
+        let file = dir.join("args.rsp");
+        let content = concat!(
+            "\u{FEFF}# a comment\r\n",
+            "--verbose\r\n",
+            "\n",
+            "   --ignore=foo.dll   \n",
+            "\"C:\\\\Program Files\\\\app.exe\"\n",
+            "\"say \\\"hi\\\" \\n\"\n",
+            "  # indented comment\n",
+            "\"# not a comment\"",
+        );
+        std::fs::write(&file, content).unwrap();
+
+        let rsp = format!("@{}", file.display());
+        let args = expand(&["deploy-dll", &rsp, "--scan"]).unwrap();
+        assert_eq!(
+            args,
+            [
+                "deploy-dll",
+                "--verbose",
+                "--ignore=foo.dll",
+                r"C:\Program Files\app.exe",
+                r#"say "hi" \n"#,
+                "# not a comment",
+                "--scan",
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_bad_response_files() {
+        let dir = test_dir("bad-response-files");
+        let file = dir.join("args.rsp");
+        std::fs::write(&file, "--verbose\n\"unterminated\n").unwrap();
+        let rsp = format!("@{}", file.display());
+        match expand(&["deploy-dll", &rsp]) {
+            Err(Error::Parse { message, .. }) => assert_eq!(message, "line 2: unterminated quote"),
+            other => panic!("unexpected result {other:?}"),
+        }
+
+        let missing = format!("@{}", dir.join("missing.rsp").display());
+        assert!(matches!(
+            expand(&["deploy-dll", &missing]),
+            Err(Error::Io { .. })
+        ));
+    }
+
+    #[test]
+    fn skips_program_name_and_unescapes_at() {
+        let args = expand(&["@deploy-dll", "@@app.exe", "@@@x", "a@b"]).unwrap();
+        assert_eq!(args, ["@deploy-dll", "@app.exe", "@@x", "a@b"]);
+        assert!(expand(&[]).unwrap().is_empty());
+    }
+}
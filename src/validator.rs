@@ -0,0 +1,813 @@
+use crate::manifest::sha256_hex;
+use crate::msvc::{classify_runtime_dll, RuntimeComponent};
+use crate::pe::{machine_name, ImportedSymbol};
+use crate::util::long_path;
+use pelite::PeFile;
+use serde::Serialize;
+use std::cell::OnceCell;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// What a candidate dll is expected to provide.
+#[derive(Debug, Clone, Copy)]
+pub struct ValidationRequest<'a> {
+    /// Lower case filename of the dll
+    pub dll_name: &'a str,
+    /// The binary that imports the dll
+    pub importer: &'a Path,
+    /// Machine type of the importer, `None` if it can't be read
+    pub machine: Option<u16>,
+    /// Linker version of the importer, `None` if it can't be read
+    pub linker_version: Option<(u8, u8)>,
+    /// Symbols the importer imports from the dll
+    pub symbols: &'a [ImportedSymbol],
+}
+
+/// A candidate dll, parsed once from a mapped view and shared by every validator.
+pub struct Candidate<'a> {
+    pub path: &'a Path,
+    pub image: Result<PeFile<'a>, pelite::Error>,
+    /// Content of the file, only read if a validator asks for it
+    bytes: OnceCell<Result<Vec<u8>, String>>,
+}
+
+impl Candidate<'_> {
+    /// The whole content of the file, read on the first call.
+    pub fn bytes(&self) -> Result<&[u8], Rejection> {
+        let bytes = self.bytes.get_or_init(|| {
+            return std::fs::read(long_path(self.path)).map_err(|e| e.to_string());
+        });
+        return match bytes {
+            Ok(bytes) => Ok(bytes),
+            Err(error) => Err(Rejection::Unreadable {
+                error: error.clone(),
+            }),
+        };
+    }
+}
+
+/// Why a candidate dll is rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Rejection {
+    Unreadable {
+        error: String,
+    },
+    NotPe {
+        error: String,
+    },
+    MachineMismatch {
+        expected: String,
+        found: String,
+    },
+    FileVersionTooOld {
+        minimum: String,
+        found: Option<String>,
+    },
+    MissingExports {
+        symbols: Vec<String>,
+    },
+    RuntimeTooOld {
+        required: String,
+        found: Option<String>,
+    },
+    Unsigned,
+    HashNotAllowed {
+        sha256: String,
+    },
+    /// Rejected by a user-supplied validator
+    Custom {
+        reason: String,
+    },
+}
+
+impl std::fmt::Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            Rejection::Unreadable { error } => write!(f, "it can't be read: {error}"),
+            Rejection::NotPe { error } => write!(f, "it is not a valid PE file: {error}"),
+            Rejection::MachineMismatch { expected, found } => {
+                write!(f, "it is built for {found}, but {expected} is required")
+            }
+            Rejection::FileVersionTooOld { minimum, found } => match found {
+                Some(found) => write!(f, "its file version {found} is lower than {minimum}"),
+                None => write!(f, "it has no file version, {minimum} is required"),
+            },
+            Rejection::MissingExports { symbols } => {
+                const SHOWN: usize = 5;
+                write!(f, "it doesn't export ")?;
+                write!(f, "{}", symbols[..symbols.len().min(SHOWN)].join(", "))?;
+                if symbols.len() > SHOWN {
+                    write!(f, " and {} more", symbols.len() - SHOWN)?;
+                }
+                return Ok(());
+            }
+            Rejection::RuntimeTooOld { required, found } => match found {
+                Some(found) => write!(
+                    f,
+                    "it is from toolset {found}, but the importer is linked by toolset {required}"
+                ),
+                None => write!(f, "it has no file version, toolset {required} is required"),
+            },
+            Rejection::Unsigned => write!(f, "it is not signed"),
+            Rejection::HashNotAllowed { sha256 } => {
+                write!(f, "its sha256 {sha256} is not in the allowlist")
+            }
+            Rejection::Custom { reason } => write!(f, "{reason}"),
+        };
+    }
+}
+
+/// A candidate dll rejected during resolution.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RejectedCandidate {
+    /// Lower case filename of the dll
+    pub dll_name: String,
+    pub path: PathBuf,
+    /// Name of the validator that rejected it
+    pub validator: String,
+    pub reason: Rejection,
+    pub required_by: PathBuf,
+}
+
+/// A check on candidate dlls. Rejected candidates are skipped and the search goes on.
+pub trait Validator: Send + Sync {
+    /// Recorded in every rejection by this validator
+    fn name(&self) -> &str;
+
+    fn validate(&self, candidate: &Candidate, request: &ValidationRequest)
+        -> Result<(), Rejection>;
+}
+
+impl std::fmt::Debug for dyn Validator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "Validator({})", self.name());
+    }
+}
+
+/// Built-in validators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValidatorKind {
+    /// The candidate is a valid PE file
+    Pe,
+    /// The candidate is built for the same machine as the importer
+    Machine,
+    /// The file version of the candidate is not lower than a minimum
+    FileVersion,
+    /// The candidate exports every symbol the importer imports from it
+    Exports,
+    /// Dlls of the VC runtime are not older than the toolset that links the importer
+    RuntimeVersion,
+    /// The candidate has an embedded Authenticode signature, a PKCS#7 SignedData blob in its
+    /// certificate table. Only its presence is checked, the signature is not verified
+    HasSignature,
+    /// The sha256 of the candidate is in an allowlist
+    Hash,
+}
+
+impl ValidatorKind {
+    pub const ALL: [ValidatorKind; 7] = [
+        ValidatorKind::Pe,
+        ValidatorKind::Machine,
+        ValidatorKind::FileVersion,
+        ValidatorKind::Exports,
+        ValidatorKind::RuntimeVersion,
+        ValidatorKind::HasSignature,
+        ValidatorKind::Hash,
+    ];
+
+    /// Validators enabled by default
+    pub const DEFAULT: [ValidatorKind; 3] = [
+        ValidatorKind::Pe,
+        ValidatorKind::Machine,
+        ValidatorKind::RuntimeVersion,
+    ];
+
+    pub fn name(self) -> &'static str {
+        return match self {
+            ValidatorKind::Pe => "pe",
+            ValidatorKind::Machine => "machine",
+            ValidatorKind::FileVersion => "file-version",
+            ValidatorKind::Exports => "exports",
+            ValidatorKind::RuntimeVersion => "runtime-version",
+            ValidatorKind::HasSignature => "has-signature",
+            ValidatorKind::Hash => "hash",
+        };
+    }
+}
+
+impl std::fmt::Display for ValidatorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "{}", self.name());
+    }
+}
+
+impl FromStr for ValidatorKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        for kind in ValidatorKind::ALL {
+            if kind.name() == s {
+                return Ok(kind);
+            }
+        }
+        let names: Vec<&str> = ValidatorKind::ALL.iter().map(|k| k.name()).collect();
+        return Err(format!(
+            "Unknown validator \"{s}\", expected one of {}",
+            names.join(", ")
+        ));
+    }
+}
+
+/// A file version like `1.2.3.4`, compared from the major version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FileVersion(pub [u16; 4]);
+
+impl std::fmt::Display for FileVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let [major, minor, patch, build] = self.0;
+        return write!(f, "{major}.{minor}.{patch}.{build}");
+    }
+}
+
+impl FromStr for FileVersion {
+    type Err = String;
+
+    /// Missing parts are 0, for example `14.38` is `14.38.0.0`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut version = [0u16; 4];
+        let parts: Vec<&str> = s.trim().split('.').collect();
+        if parts.len() > 4 {
+            return Err(format!("\"{s}\" has more than 4 parts"));
+        }
+        for (index, part) in parts.iter().enumerate() {
+            version[index] = part
+                .parse()
+                .map_err(|e| format!("\"{s}\" is not a valid file version: {e}"))?;
+        }
+        return Ok(FileVersion(version));
+    }
+}
+
+/// The file version in the version resource of a PE file.
+pub fn file_version(image: &PeFile) -> Option<FileVersion> {
+    let fixed = image.resources().ok()?.version_info().ok()?.fixed()?;
+    let v = fixed.dwFileVersion;
+    return Some(FileVersion([v.Major, v.Minor, v.Patch, v.Build]));
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PeValidator;
+
+impl Validator for PeValidator {
+    fn name(&self) -> &str {
+        return ValidatorKind::Pe.name();
+    }
+
+    fn validate(&self, candidate: &Candidate, _: &ValidationRequest) -> Result<(), Rejection> {
+        if let Err(e) = &candidate.image {
+            return Err(Rejection::NotPe {
+                error: e.to_string(),
+            });
+        }
+        return Ok(());
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MachineValidator;
+
+impl Validator for MachineValidator {
+    fn name(&self) -> &str {
+        return ValidatorKind::Machine.name();
+    }
+
+    fn validate(
+        &self,
+        candidate: &Candidate,
+        request: &ValidationRequest,
+    ) -> Result<(), Rejection> {
+        let (Ok(image), Some(expected)) = (&candidate.image, request.machine) else {
+            return Ok(());
+        };
+        let found = image.file_header().Machine;
+        if found == expected {
+            return Ok(());
+        }
+        return Err(Rejection::MachineMismatch {
+            expected: machine_name(expected),
+            found: machine_name(found),
+        });
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MinFileVersionValidator {
+    pub minimum: FileVersion,
+}
+
+impl Validator for MinFileVersionValidator {
+    fn name(&self) -> &str {
+        return ValidatorKind::FileVersion.name();
+    }
+
+    fn validate(&self, candidate: &Candidate, _: &ValidationRequest) -> Result<(), Rejection> {
+        let found = candidate.image.as_ref().ok().and_then(file_version);
+        if let Some(found) = found {
+            if found >= self.minimum {
+                return Ok(());
+            }
+        }
+        return Err(Rejection::FileVersionTooOld {
+            minimum: self.minimum.to_string(),
+            found: found.map(|v| v.to_string()),
+        });
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ExportsValidator;
+
+impl Validator for ExportsValidator {
+    fn name(&self) -> &str {
+        return ValidatorKind::Exports.name();
+    }
+
+    fn validate(
+        &self,
+        candidate: &Candidate,
+        request: &ValidationRequest,
+    ) -> Result<(), Rejection> {
+        let Ok(image) = &candidate.image else {
+            return Ok(());
+        };
+        let by = image.exports().and_then(|e| e.by()).ok();
+        let missing: Vec<String> = request
+            .symbols
+            .iter()
+            .filter(|symbol| {
+                let Some(by) = &by else {
+                    return true;
+                };
+                return match symbol {
+                    ImportedSymbol::Name(name) => by.name(name.as_str()).is_err(),
+                    ImportedSymbol::Ordinal(ord) => by.ordinal(*ord).is_err(),
+                };
+            })
+            .map(|symbol| symbol.to_string())
+            .collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+        return Err(Rejection::MissingExports { symbols: missing });
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RuntimeVersionValidator;
+
+impl Validator for RuntimeVersionValidator {
+    fn name(&self) -> &str {
+        return ValidatorKind::RuntimeVersion.name();
+    }
+
+    fn validate(
+        &self,
+        candidate: &Candidate,
+        request: &ValidationRequest,
+    ) -> Result<(), Rejection> {
+        let (Ok(image), Some((major, minor))) = (&candidate.image, request.linker_version) else {
+            return Ok(());
+        };
+        // Only dlls of version 140 are shared by several toolsets, and their file versions
+        // follow the toolset version
+        let is_vc140 = classify_runtime_dll(request.dll_name).is_some_and(|dll| {
+            return dll.version == Some(140) && dll.component != RuntimeComponent::MfcLocale;
+        });
+        if !is_vc140 || major != 14 {
+            return Ok(());
+        }
+        let found = file_version(image);
+        if let Some(found) = found {
+            if (found.0[0], found.0[1]) >= (major as u16, minor as u16) {
+                return Ok(());
+            }
+        }
+        return Err(Rejection::RuntimeTooOld {
+            required: format!("{major}.{minor}"),
+            found: found.map(|v| format!("{}.{}", v.0[0], v.0[1])),
+        });
+    }
+}
+
+/// Accepts candidates whose certificate table holds a PKCS#7 SignedData blob. The signature is
+/// not verified, a tampered file with a well-formed blob is still accepted.
+#[derive(Debug, Clone, Copy)]
+pub struct HasSignatureValidator;
+
+impl Validator for HasSignatureValidator {
+    fn name(&self) -> &str {
+        return ValidatorKind::HasSignature.name();
+    }
+
+    fn validate(&self, candidate: &Candidate, _: &ValidationRequest) -> Result<(), Rejection> {
+        let security = match &candidate.image {
+            Ok(image) => image.security().ok(),
+            Err(_) => None,
+        };
+        let signed = security.is_some_and(|security| {
+            let certificate = security.image();
+            return is_signed_data_certificate(
+                certificate.wRevision,
+                certificate.wCertificateType,
+                certificate.dwLength,
+                security.certificate_data(),
+            );
+        });
+        if signed {
+            return Ok(());
+        }
+        return Err(Rejection::Unsigned);
+    }
+}
+
+/// Whether a `WIN_CERTIFICATE` holds a DER encoded PKCS#7 ContentInfo of type SignedData.
+fn is_signed_data_certificate(
+    revision: u16,
+    certificate_type: u16,
+    length: u32,
+    data: &[u8],
+) -> bool {
+    const WIN_CERT_REVISIONS: [u16; 2] = [0x0100, 0x0200];
+    const WIN_CERT_TYPE_PKCS_SIGNED_DATA: u16 = 0x0002;
+    /// OID 1.2.840.113549.1.7.2, signedData
+    const SIGNED_DATA_OID: [u8; 11] = [
+        0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x07, 0x02,
+    ];
+    if !WIN_CERT_REVISIONS.contains(&revision) || certificate_type != WIN_CERT_TYPE_PKCS_SIGNED_DATA
+    {
+        return false;
+    }
+    // dwLength includes the 8 bytes of the header
+    let data = match (length as usize).checked_sub(8).and_then(|n| data.get(..n)) {
+        Some(data) => data,
+        None => return false,
+    };
+    // ContentInfo ::= SEQUENCE { contentType OBJECT IDENTIFIER, ... }
+    if data.first() != Some(&0x30) {
+        return false;
+    }
+    let (content_length, header) = match data.get(1) {
+        Some(&n) if n < 0x80 => (n as usize, 2),
+        Some(&n) if (0x81..=0x84).contains(&n) => {
+            let count = (n & 0x7F) as usize;
+            let bytes = match data.get(2..2 + count) {
+                Some(bytes) => bytes,
+                None => return false,
+            };
+            let length = bytes.iter().fold(0usize, |l, b| (l << 8) | *b as usize);
+            (length, 2 + count)
+        }
+        _ => return false,
+    };
+    return match data.get(header..) {
+        Some(content) => content.len() >= content_length && content.starts_with(&SIGNED_DATA_OID),
+        None => false,
+    };
+}
+
+#[derive(Debug, Clone)]
+pub struct HashAllowlistValidator {
+    /// Lower case hex sha256
+    pub allowed: HashSet<String>,
+}
+
+impl Validator for HashAllowlistValidator {
+    fn name(&self) -> &str {
+        return ValidatorKind::Hash.name();
+    }
+
+    fn validate(&self, candidate: &Candidate, _: &ValidationRequest) -> Result<(), Rejection> {
+        let sha256 = sha256_hex(candidate.bytes()?);
+        if self.allowed.contains(&sha256) {
+            return Ok(());
+        }
+        return Err(Rejection::HashNotAllowed { sha256 });
+    }
+}
+
+/// Validators run in order, the first rejection wins.
+#[derive(Debug, Clone, Default)]
+pub struct ValidatorPipeline {
+    validators: Vec<Arc<dyn Validator>>,
+}
+
+impl ValidatorPipeline {
+    pub fn new(validators: Vec<Arc<dyn Validator>>) -> ValidatorPipeline {
+        return ValidatorPipeline { validators };
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.validators.is_empty();
+    }
+
+    /// Returns the name of the validator and the reason if the candidate is rejected.
+    pub fn check(
+        &self,
+        path: &Path,
+        request: &ValidationRequest,
+    ) -> Result<(), (String, Rejection)> {
+        if self.validators.is_empty() {
+            return Ok(());
+        }
+        let map = pelite::FileMap::open(&long_path(path)).map_err(|e| {
+            let rejection = Rejection::Unreadable {
+                error: e.to_string(),
+            };
+            return (ValidatorKind::Pe.name().to_string(), rejection);
+        })?;
+        let candidate = Candidate {
+            path,
+            image: PeFile::from_bytes(&map),
+            bytes: OnceCell::new(),
+        };
+        for validator in &self.validators {
+            validator
+                .validate(&candidate, request)
+                .map_err(|rejection| (validator.name().to_string(), rejection))?;
+        }
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_pe::TestPe;
+    use crate::util::test_dir;
+
+    /// ContentInfo of type signedData with `extra` bytes of content after the OID
+    fn signed_data(extra: usize) -> Vec<u8> {
+        let mut content = vec![
+            0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x07, 0x02,
+        ];
+        content.resize(content.len() + extra, 0);
+        let mut der = vec![0x30];
+        if content.len() < 0x80 {
+            der.push(content.len() as u8);
+        } else {
+            der.extend([0x82, (content.len() >> 8) as u8, content.len() as u8]);
+        }
+        der.extend(content);
+        return der;
+    }
+
+    #[test]
+    fn accepts_signed_data() {
+        for extra in [0, 200] {
+            let data = signed_data(extra);
+            let length = data.len() as u32 + 8;
+            assert!(is_signed_data_certificate(0x0200, 2, length, &data));
+        }
+    }
+
+    #[test]
+    fn accepts_padding_after_certificate() {
+        let mut data = signed_data(5);
+        let length = data.len() as u32 + 8;
+        data.resize(data.len() + 7, 0);
+        assert!(is_signed_data_certificate(0x0200, 2, length, &data));
+    }
+
+    #[test]
+    fn rejects_other_certificate_types_and_revisions() {
+        let data = signed_data(5);
+        let length = data.len() as u32 + 8;
+        assert!(!is_signed_data_certificate(0x0200, 1, length, &data));
+        assert!(!is_signed_data_certificate(0x0300, 2, length, &data));
+    }
+
+    #[test]
+    fn rejects_padding_and_truncated_data() {
+        assert!(!is_signed_data_certificate(0x0200, 2, 8 + 16, &[0; 16]));
+        let data = signed_data(20);
+        assert!(!is_signed_data_certificate(0x0200, 2, 8 + 10, &data[..10]));
+        // dwLength points past the end of the table
+        assert!(!is_signed_data_certificate(0x0200, 2, 8 + 100, &data));
+        assert!(!is_signed_data_certificate(0x0200, 2, 4, &data));
+    }
+
+    #[test]
+    fn rejects_other_content_types() {
+        let mut data = signed_data(5);
+        // OID 1.2.840.113549.1.7.1, data
+        data[12] = 0x01;
+        let length = data.len() as u32 + 8;
+        assert!(!is_signed_data_certificate(0x0200, 2, length, &data));
+    }
+
+    fn request<'a>(dll_name: &'a str, symbols: &'a [ImportedSymbol]) -> ValidationRequest<'a> {
+        return ValidationRequest {
+            dll_name,
+            importer: Path::new("app.exe"),
+            machine: Some(0x8664),
+            linker_version: Some((14, 38)),
+            symbols,
+        };
+    }
+
+    /// Write `good` and `bad` into `dir`, and check that only `bad` is rejected by `validator`
+    fn check_validator(
+        dir: &Path,
+        validator: impl Validator + 'static,
+        request: &ValidationRequest,
+        good: TestPe,
+        bad: TestPe,
+    ) -> Rejection {
+        let pipeline = ValidatorPipeline::new(vec![Arc::new(validator)]);
+        let (good_path, bad_path) = (dir.join("good.dll"), dir.join("bad.dll"));
+        good.write(&good_path);
+        bad.write(&bad_path);
+        assert_eq!(pipeline.check(&good_path, request), Ok(()));
+        let (name, rejection) = pipeline.check(&bad_path, request).unwrap_err();
+        assert_eq!(name, pipeline.validators[0].name());
+        return rejection;
+    }
+
+    #[test]
+    fn rejects_files_that_are_not_pe() {
+        let dir = test_dir("validator-pe");
+        let pipeline = ValidatorPipeline::new(vec![Arc::new(PeValidator)]);
+        let request = request("foo.dll", &[]);
+        TestPe::dll().write(&dir.join("foo.dll"));
+        assert_eq!(pipeline.check(&dir.join("foo.dll"), &request), Ok(()));
+
+        std::fs::write(dir.join("text.dll"), "not a PE file").unwrap();
+        let rejected = pipeline.check(&dir.join("text.dll"), &request);
+        assert!(matches!(rejected, Err((name, Rejection::NotPe { .. })) if name == "pe"));
+
+        let rejected = pipeline.check(&dir.join("missing.dll"), &request);
+        assert!(matches!(rejected, Err((name, Rejection::Unreadable { .. })) if name == "pe"));
+    }
+
+    #[test]
+    fn rejects_other_machines() {
+        let rejection = check_validator(
+            &test_dir("validator-machine"),
+            MachineValidator,
+            &request("foo.dll", &[]),
+            TestPe::dll(),
+            TestPe::dll().machine(0xAA64),
+        );
+        let expected = Rejection::MachineMismatch {
+            expected: "x64".to_string(),
+            found: "arm64".to_string(),
+        };
+        assert_eq!(rejection, expected);
+    }
+
+    #[test]
+    fn rejects_old_file_versions() {
+        let dir = test_dir("validator-file-version");
+        let validator = MinFileVersionValidator {
+            minimum: "1.2".parse().unwrap(),
+        };
+        let request = request("foo.dll", &[]);
+        let good = TestPe::dll().file_version("1.2.0.1");
+        let bad = TestPe::dll().file_version("1.1.9.0");
+        let rejection = check_validator(&dir, validator, &request, good.clone(), bad);
+        let expected = Rejection::FileVersionTooOld {
+            minimum: "1.2.0.0".to_string(),
+            found: Some("1.1.9.0".to_string()),
+        };
+        assert_eq!(rejection, expected);
+
+        let rejection = check_validator(&dir, validator, &request, good, TestPe::dll());
+        assert!(matches!(
+            rejection,
+            Rejection::FileVersionTooOld { found: None, .. }
+        ));
+    }
+
+    #[test]
+    fn rejects_missing_exports() {
+        let symbols = [
+            ImportedSymbol::Name("foo".to_string()),
+            ImportedSymbol::Name("bar".to_string()),
+            ImportedSymbol::Ordinal(2),
+        ];
+        let rejection = check_validator(
+            &test_dir("validator-exports"),
+            ExportsValidator,
+            &request("foo.dll", &symbols),
+            TestPe::dll().export("foo").export("bar"),
+            TestPe::dll().export("foo"),
+        );
+        let expected = Rejection::MissingExports {
+            symbols: vec!["bar".to_string(), "#2".to_string()],
+        };
+        assert_eq!(rejection, expected);
+    }
+
+    #[test]
+    fn rejects_runtime_dlls_older_than_the_linker() {
+        let dir = test_dir("validator-runtime-version");
+        let request = request("msvcp140.dll", &[]);
+        let good = TestPe::dll().file_version("14.40.33810.0");
+        let bad = TestPe::dll().file_version("14.29.30139.0");
+        let rejection = check_validator(&dir, RuntimeVersionValidator, &request, good, bad);
+        let expected = Rejection::RuntimeTooOld {
+            required: "14.38".to_string(),
+            found: Some("14.29".to_string()),
+        };
+        assert_eq!(rejection, expected);
+
+        // Only dlls of version 140 follow the toolset version
+        let pipeline = ValidatorPipeline::new(vec![Arc::new(RuntimeVersionValidator)]);
+        let request = ValidationRequest {
+            dll_name: "zlib1.dll",
+            ..request
+        };
+        assert_eq!(pipeline.check(&dir.join("bad.dll"), &request), Ok(()));
+    }
+
+    #[test]
+    fn rejects_unsigned_dlls() {
+        let rejection = check_validator(
+            &test_dir("validator-signature"),
+            HasSignatureValidator,
+            &request("foo.dll", &[]),
+            TestPe::dll().signed(),
+            TestPe::dll(),
+        );
+        assert_eq!(rejection, Rejection::Unsigned);
+    }
+
+    #[test]
+    fn rejects_hashes_not_in_allowlist() {
+        let good = TestPe::dll().export("foo");
+        let validator = HashAllowlistValidator {
+            allowed: HashSet::from([sha256_hex(&good.bytes())]),
+        };
+        let rejection = check_validator(
+            &test_dir("validator-hash"),
+            validator,
+            &request("foo.dll", &[]),
+            good,
+            TestPe::dll(),
+        );
+        let expected = Rejection::HashNotAllowed {
+            sha256: sha256_hex(&TestPe::dll().bytes()),
+        };
+        assert_eq!(rejection, expected);
+    }
+
+    struct RejectAll;
+
+    impl Validator for RejectAll {
+        fn name(&self) -> &str {
+            return "reject-all";
+        }
+
+        fn validate(&self, _: &Candidate, _: &ValidationRequest) -> Result<(), Rejection> {
+            return Err(Rejection::Custom {
+                reason: "rejected".to_string(),
+            });
+        }
+    }
+
+    #[test]
+    fn stops_at_first_rejection() {
+        let dir = test_dir("validator-pipeline");
+        let path = dir.join("foo.dll");
+        TestPe::dll().machine(0x14c).write(&path);
+        let request = request("foo.dll", &[]);
+
+        let pipeline = ValidatorPipeline::new(vec![
+            Arc::new(PeValidator),
+            Arc::new(RejectAll),
+            Arc::new(MachineValidator),
+        ]);
+        let custom = Rejection::Custom {
+            reason: "rejected".to_string(),
+        };
+        assert_eq!(
+            pipeline.check(&path, &request),
+            Err(("reject-all".to_string(), custom))
+        );
+
+        let pipeline =
+            ValidatorPipeline::new(vec![Arc::new(MachineValidator), Arc::new(RejectAll)]);
+        let rejected = pipeline.check(&path, &request);
+        assert!(matches!(rejected, Err((name, _)) if name == "machine"));
+
+        // An empty pipeline doesn't even read the file
+        let missing = dir.join("missing.dll");
+        assert_eq!(
+            ValidatorPipeline::default().check(&missing, &request),
+            Ok(())
+        );
+    }
+}
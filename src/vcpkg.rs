@@ -0,0 +1,249 @@
+use crate::resolver::BuildConfig;
+use crate::util::{can_be_dir, is_file, long_path, subdirs, walk_dir};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// A triplet dir of a vcpkg installation, like `vcpkg/installed/x64-windows`, passed as a CMake
+/// prefix.
+#[derive(Debug, Clone)]
+pub struct VcpkgPrefix {
+    pub prefix: PathBuf,
+    pub triplet: String,
+}
+
+impl VcpkgPrefix {
+    /// Recognize a triplet dir by `vcpkg/status` next to it, or by
+    /// `share/<port>/vcpkg_abi_info.txt` in it.
+    pub fn detect(prefix: &Path) -> Option<VcpkgPrefix> {
+        let triplet = prefix.file_name()?.to_string_lossy().to_string();
+        let installed = prefix.parent()?;
+        let is_vcpkg =
+            is_file(&installed.join("vcpkg/status")) || !ports_in_share(prefix).is_empty();
+        if !is_vcpkg {
+            return None;
+        }
+        return Some(VcpkgPrefix {
+            prefix: prefix.to_path_buf(),
+            triplet,
+        });
+    }
+
+    /// Dirs with dlls for binaries of `config`, in the order they are searched. Dlls of the
+    /// other config are searched after them, so that a dll only built in one config is still
+    /// found. Plugins are searched recursively, `tools/<port>` are searched last.
+    pub fn dll_dirs(&self, config: BuildConfig) -> Vec<PathBuf> {
+        let release = [self.prefix.join("bin"), self.prefix.join("plugins")];
+        let debug = [
+            self.prefix.join("debug/bin"),
+            self.prefix.join("debug/plugins"),
+        ];
+        let ordered = match config {
+            BuildConfig::Release => release.iter().chain(debug.iter()),
+            BuildConfig::Debug => debug.iter().chain(release.iter()),
+        };
+        let mut dirs: Vec<PathBuf> = Vec::new();
+        for dir in ordered {
+            dirs.extend(dirs_with_dlls(dir));
+        }
+        dirs.extend(subdirs(&self.prefix.join("tools")));
+        return dirs;
+    }
+
+    /// Ports installed for the triplet, by `share/<port>/vcpkg_abi_info.txt`
+    pub fn ports(&self) -> Vec<String> {
+        return ports_in_share(&self.prefix);
+    }
+
+    /// Owning port of every file installed for the triplet, keyed by [`path_key`]. Read from
+    /// `vcpkg/info/<port>_<version>_<triplet>.list`, which vcpkg writes for installed ports.
+    pub fn owners(&self) -> HashMap<String, String> {
+        let mut owners = HashMap::new();
+        let installed = match self.prefix.parent() {
+            Some(dir) => dir,
+            None => return owners,
+        };
+        let info_dir = installed.join("vcpkg/info");
+        let entries = match std::fs::read_dir(long_path(&info_dir)) {
+            Ok(entries) => entries,
+            Err(_) => return owners,
+        };
+        let suffix = format!("_{}.list", self.triplet);
+        for entry in entries.filter_map(|e| e.ok()) {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !name.ends_with(&suffix) {
+                continue;
+            }
+            let port = match name.split_once('_') {
+                Some((port, _)) => port.to_string(),
+                None => continue,
+            };
+            let text = match std::fs::read_to_string(long_path(&info_dir.join(entry.file_name()))) {
+                Ok(text) => text,
+                Err(_) => continue,
+            };
+            for line in text.lines() {
+                let line = line.trim();
+                if line.to_lowercase().ends_with(".dll") {
+                    owners.insert(path_key(&installed.join(line)), port.clone());
+                }
+            }
+        }
+        return owners;
+    }
+}
+
+/// Lower case path with `/` separators, to look up owners of files.
+pub fn path_key(path: &Path) -> String {
+    return path.to_string_lossy().replace('\\', "/").to_lowercase();
+}
+
+fn ports_in_share(prefix: &Path) -> Vec<String> {
+    return subdirs(&prefix.join("share"))
+        .iter()
+        .filter(|d| is_file(&d.join("vcpkg_abi_info.txt")))
+        .filter_map(|d| Some(d.file_name()?.to_string_lossy().to_string()))
+        .collect();
+}
+
+/// `dir` and its sub dirs that have dlls in them
+fn dirs_with_dlls(dir: &Path) -> Vec<PathBuf> {
+    if !can_be_dir(&dir) {
+        return Vec::new();
+    }
+    let mut dirs = vec![dir.to_path_buf()];
+    for entry in walk_dir(dir, |w| w.min_depth(1).sort_by_file_name()) {
+        let (path, entry) = match entry {
+            Ok(entry) => entry,
+            Err(_) => continue,
+        };
+        let is_dll = entry
+            .file_name()
+            .to_string_lossy()
+            .to_lowercase()
+            .ends_with(".dll");
+        if let (true, Some(parent)) = (is_dll, path.parent()) {
+            if !dirs.iter().any(|d| d == parent) {
+                dirs.push(parent.to_path_buf());
+            }
+        }
+    }
+    return dirs;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::test_dir;
+
+    fn touch(path: &Path) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, "").unwrap();
+    }
+
+    #[test]
+    fn detects_triplet_dirs() {
+        let dir = test_dir("vcpkg-detect");
+        let by_status = dir.join("a/installed/x64-windows");
+        std::fs::create_dir_all(&by_status).unwrap();
+        touch(&dir.join("a/installed/vcpkg/status"));
+        let prefix = VcpkgPrefix::detect(&by_status).unwrap();
+        assert_eq!(prefix.triplet, "x64-windows");
+        assert_eq!(prefix.prefix, by_status);
+
+        let by_abi_info = dir.join("b/x64-windows-static-md");
+        touch(&by_abi_info.join("share/zlib/vcpkg_abi_info.txt"));
+        let prefix = VcpkgPrefix::detect(&by_abi_info).unwrap();
+        assert_eq!(prefix.triplet, "x64-windows-static-md");
+
+        let not_vcpkg = dir.join("c/prefix");
+        touch(&not_vcpkg.join("share/zlib/zlib-config.cmake"));
+        assert!(VcpkgPrefix::detect(&not_vcpkg).is_none());
+    }
+
+    #[test]
+    fn lists_dll_dirs_ports_and_owners() {
+        let dir = test_dir("vcpkg-triplet");
+        let installed = dir.join("installed");
+        let triplet = installed.join("x64-windows");
+        for file in [
+            "bin/zlib1.dll",
+            "debug/bin/zlibd1.dll",
+            "plugins/platforms/qwindows.dll",
+            "plugins/empty/readme.txt",
+            "debug/plugins/platforms/qwindowsd.dll",
+            "tools/protobuf/protoc.exe",
+            "share/zlib/vcpkg_abi_info.txt",
+            "share/qtbase/vcpkg_abi_info.txt",
+            "share/cmake/readme.txt",
+        ] {
+            touch(&triplet.join(file));
+        }
+        let list = installed.join("vcpkg/info/zlib_1.3.1_x64-windows.list");
+        touch(&list);
+        std::fs::write(
+            &list,
+            "x64-windows/\nx64-windows/bin/zlib1.dll\nx64-windows/debug/bin/zlibd1.dll\n\
+             x64-windows/include/zlib.h\n",
+        )
+        .unwrap();
+        touch(&installed.join("vcpkg/info/zlib_1.3.1_x86-windows.list"));
+        let prefix = VcpkgPrefix::detect(&triplet).unwrap();
+
+        let relative = |dirs: Vec<PathBuf>| -> Vec<String> {
+            return dirs
+                .iter()
+                .map(|d| {
+                    d.strip_prefix(&triplet)
+                        .unwrap()
+                        .to_string_lossy()
+                        .to_string()
+                })
+                .collect();
+        };
+        assert_eq!(
+            relative(prefix.dll_dirs(BuildConfig::Release)),
+            [
+                "bin",
+                "plugins",
+                "plugins/platforms",
+                "debug/bin",
+                "debug/plugins",
+                "debug/plugins/platforms",
+                "tools/protobuf",
+            ]
+        );
+        assert_eq!(
+            relative(prefix.dll_dirs(BuildConfig::Debug)),
+            [
+                "debug/bin",
+                "debug/plugins",
+                "debug/plugins/platforms",
+                "bin",
+                "plugins",
+                "plugins/platforms",
+                "tools/protobuf",
+            ]
+        );
+
+        assert_eq!(prefix.ports(), ["qtbase", "zlib"]);
+
+        let owners = prefix.owners();
+        assert_eq!(owners.len(), 2);
+        assert_eq!(
+            owners.get(&path_key(&triplet.join("bin/zlib1.dll"))),
+            Some(&"zlib".to_string())
+        );
+        assert_eq!(
+            owners.get(&path_key(&triplet.join("debug/bin/zlibd1.dll"))),
+            Some(&"zlib".to_string())
+        );
+    }
+
+    #[test]
+    fn keys_paths_case_and_separator_insensitively() {
+        assert_eq!(
+            path_key(Path::new(r"C:\vcpkg\Installed/x64-windows\bin\ZLIB1.dll")),
+            "c:/vcpkg/installed/x64-windows/bin/zlib1.dll"
+        );
+    }
+}